@@ -2,7 +2,7 @@
 #![allow(dead_code, non_camel_case_types)]
 
 /// Accelerometer settings. Use this struct to configure the sensor.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct AccelSettings {
     /// X-axis output enabled
     pub enable_x: bool,
@@ -38,6 +38,48 @@ impl Default for AccelSettings {
 }
 
 impl AccelSettings {
+    /// Decodes the settings from the values of CTRL_REG5_XL, CTRL_REG6_XL and CTRL_REG7_XL
+    pub(crate) fn from_registers(ctrl_reg5_xl: u8, ctrl_reg6_xl: u8, ctrl_reg7_xl: u8) -> Self {
+        AccelSettings {
+            enable_x: ctrl_reg5_xl & (1 << 3) != 0,
+            enable_y: ctrl_reg5_xl & (1 << 4) != 0,
+            enable_z: ctrl_reg5_xl & (1 << 5) != 0,
+            sample_rate: match (ctrl_reg6_xl & AccelBitmasks::ODR_XL) >> 5 {
+                0b001 => ODR::_10Hz,
+                0b010 => ODR::_50Hz,
+                0b011 => ODR::_119Hz,
+                0b100 => ODR::_238Hz,
+                0b101 => ODR::_476Hz,
+                0b110 => ODR::_952Hz,
+                _ => ODR::PowerDown,
+            },
+            scale: match (ctrl_reg6_xl & AccelBitmasks::FS_XL) >> 3 {
+                0b01 => Scale::_16G,
+                0b10 => Scale::_4G,
+                0b11 => Scale::_8G,
+                _ => Scale::_2G,
+            },
+            bandwidth_selection: match ctrl_reg6_xl & AccelBitmasks::BW_SCAL_ODR {
+                0 => BandwidthSelection::ByODR,
+                _ => BandwidthSelection::ByBW,
+            },
+            bandwidth: match ctrl_reg6_xl & AccelBitmasks::BW_XL {
+                0b01 => Bandwidth::_211Hz,
+                0b10 => Bandwidth::_105Hz,
+                0b11 => Bandwidth::_50Hz,
+                _ => Bandwidth::_408Hz,
+            },
+            // DCF[1:0] are only meaningful with HR set
+            high_res_bandwidth: match ctrl_reg7_xl >> 5 {
+                0b100 => HighRes::ODR_50,
+                0b101 => HighRes::ODR_100,
+                0b110 => HighRes::ODR_9,
+                0b111 => HighRes::ODR_400,
+                _ => HighRes::Disabled,
+            },
+        }
+    }
+
     /// Returns `u8` to write to CTRL_REG5_XL (0x1F)
     /// # CTRL_REG5_XL: [DEC_1][DEC_0][Zen_XL][Yen_XL][Xen_XL][0][0][0]
     /// - DEC[0:1] - Decimation of accel data on OUT REG and FIFO.
@@ -86,6 +128,20 @@ impl AccelSettings {
     }
 }
 
+/// Bitmasks for the fields of CTRL_REG6_XL
+pub(crate) struct AccelBitmasks;
+
+impl AccelBitmasks {
+    /// ODR_XL[2:0]
+    pub(crate) const ODR_XL: u8 = 0b1110_0000;
+    /// FS_XL[1:0]
+    pub(crate) const FS_XL: u8 = 0b0001_1000;
+    /// BW_SCAL_ODR
+    pub(crate) const BW_SCAL_ODR: u8 = 0b0000_0100;
+    /// BW_XL[1:0]
+    pub(crate) const BW_XL: u8 = 0b0000_0011;
+}
+
 /// Accelerometer full-scale selection. (Refer to Table 67)
 #[derive(Debug, Clone, Copy)]
 pub enum Scale {
@@ -139,10 +195,24 @@ impl ODR {
     pub fn value(self) -> u8 {
         (self as u8) << 5
     }
+
+    /// Returns the output data rate in Hz (0 in power-down mode)
+    pub fn hz(self) -> f32 {
+        use ODR::*;
+        match self {
+            PowerDown => 0.0,
+            _10Hz => 10.0,
+            _50Hz => 50.0,
+            _119Hz => 119.0,
+            _238Hz => 238.0,
+            _476Hz => 476.0,
+            _952Hz => 952.0,
+        }
+    }
 }
 
 /// Bandwidth selection. (Refer to Table 67)
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum BandwidthSelection {
     ByODR,
     ByBW,
@@ -325,3 +395,24 @@ fn set_accel_bandwidth() {
     };
     assert_eq!(accel.ctrl_reg6_xl() & mask, 0b0000_0000);
 }
+
+#[test]
+fn accel_settings_from_registers() {
+    let settings = AccelSettings {
+        enable_y: false,
+        sample_rate: ODR::_476Hz,
+        scale: Scale::_16G,
+        bandwidth_selection: BandwidthSelection::ByBW,
+        bandwidth: Bandwidth::_105Hz,
+        high_res_bandwidth: HighRes::ODR_9,
+        ..Default::default()
+    };
+    let decoded = AccelSettings::from_registers(
+        settings.ctrl_reg5_xl() | 0b1100_0000, // decimation bits are not part of the settings
+        settings.ctrl_reg6_xl(),
+        settings.ctrl_reg7_xl() | 0b0000_0101, // FDS, HPIS1
+    );
+    assert_eq!(decoded.ctrl_reg5_xl(), settings.ctrl_reg5_xl());
+    assert_eq!(decoded.ctrl_reg6_xl(), settings.ctrl_reg6_xl());
+    assert_eq!(decoded.ctrl_reg7_xl(), settings.ctrl_reg7_xl());
+}
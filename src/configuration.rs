@@ -1,16 +1,17 @@
 //! Configuration trait, trait implementations
 use crate::{
-    accel::AccelSettings,
+    accel::{self, AccelSettings},
     fifo::{Decimate, FIFOConfig},
-    gyro::GyroSettings,
+    gyro::{self, GyroSettings},
     interface::Sensor,
     interrupts::{
-        accel_int::IntConfigAccel,
-        gyro_int::IntConfigGyro,
+        accel_int::{IntConfigAccel, IntThresholdAccel},
+        activity::InactivityConfig,
+        gyro_int::{IntConfigGyro, IntThresholdGyro},
         mag_int::IntConfigMag,
         pins_config::{IntConfigAG1, IntConfigAG2, PinConfig},
     },
-    mag::MagSettings,
+    mag::{self, MagSettings},
     register,
 };
 
@@ -63,6 +64,17 @@ impl ConfigToWrite for IntConfigAccel {
     }
 }
 
+impl IntConfigAccel {
+    /// Returns `Configuration` to write to the accelerometer interrupt bits of CTRL_REG4
+    pub fn ctrl_reg4_config(&self) -> Configuration {
+        Configuration {
+            value: self.ctrl_reg4(),
+            sensor: Sensor::Accelerometer,
+            register: register::AG::CTRL_REG4.addr(),
+        }
+    }
+}
+
 impl ConfigToWrite for IntConfigAG1 {
     fn byte(&self) -> u8 {
         self.int1_ctrl()
@@ -123,7 +135,40 @@ impl ConfigToWrite for Decimate {
     }
 }
 
+impl ConfigToWrite for accel::Scale {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::AG::CTRL_REG6_XL.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Accelerometer
+    }
+}
+
+impl ConfigToWrite for accel::ODR {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::AG::CTRL_REG6_XL.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Accelerometer
+    }
+}
+
 impl AccelSettings {
+    /// Returns `Configuration` with the bandwidth bits (BW_SCAL_ODR, BW_XL[1:0]) of CTRL_REG6_XL
+    pub(crate) fn bandwidth_config(&self) -> Configuration {
+        Configuration {
+            value: self.bandwidth_selection.value() | self.bandwidth.value(),
+            sensor: Sensor::Accelerometer,
+            register: register::AG::CTRL_REG6_XL.addr(),
+        }
+    }
+
     /// Returns `Configuration` to write to CTRL_REG5_XL (0x1F)
     pub fn ctrl_reg5_xl_config(&self) -> Configuration {
         Configuration {
@@ -152,7 +197,64 @@ impl AccelSettings {
     }
 }
 
+impl ConfigToWrite for gyro::Scale {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::AG::CTRL_REG1_G.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Gyro
+    }
+}
+
+impl ConfigToWrite for gyro::ODR {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::AG::CTRL_REG1_G.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Gyro
+    }
+}
+
+impl ConfigToWrite for gyro::Bandwidth {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::AG::CTRL_REG1_G.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Gyro
+    }
+}
+
+impl ConfigToWrite for gyro::LowPowerMode {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::AG::CTRL_REG3_G.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Gyro
+    }
+}
+
 impl GyroSettings {
+    /// Returns `Configuration` with the high-pass filter bits (HP_EN, HPCF_G[3:0]) of CTRL_REG3_G
+    pub(crate) fn hpf_config(&self) -> Configuration {
+        Configuration {
+            value: self.hpf_mode.value() | self.hpf_cutoff.value(),
+            sensor: Sensor::Gyro,
+            register: register::AG::CTRL_REG3_G.addr(),
+        }
+    }
+
     /// Returns `Configuration` to write to CTRL_REG1_G. See page 45
     pub fn ctrl_reg1_g_config(&self) -> Configuration {
         Configuration {
@@ -185,6 +287,110 @@ impl GyroSettings {
             register: register::AG::CTRL_REG4.addr(),
         }
     }
+    /// Returns `Configuration` to write to ORIENT_CFG_G
+    pub fn orient_cfg_g_config(&self) -> Configuration {
+        Configuration {
+            value: self.orient_cfg_g(),
+            sensor: Sensor::Gyro,
+            register: register::AG::ORIENT_CFG_G.addr(),
+        }
+    }
+}
+
+impl ConfigToWrite for mag::Scale {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG2_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::ODR {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG1_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::OpModeXY {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG1_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::OpModeZ {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG4_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::SysOpMode {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG3_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::TempComp {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG1_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::FastRead {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG5_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
+}
+
+impl ConfigToWrite for mag::BlockDataUpdate {
+    fn byte(&self) -> u8 {
+        self.value()
+    }
+    fn addr(&self) -> u8 {
+        register::Mag::CTRL_REG5_M.addr()
+    }
+    fn sensor(&self) -> Sensor {
+        Sensor::Magnetometer
+    }
 }
 
 impl MagSettings {
@@ -248,3 +454,71 @@ impl FIFOConfig {
         }
     }
 }
+
+impl IntThresholdAccel {
+    /// Returns `Configuration`s to write to INT_GEN_THS_X_XL, INT_GEN_THS_Y_XL, INT_GEN_THS_Z_XL and INT_GEN_DUR_XL,
+    /// or `None` if a value cannot be represented with the given scale and output data rate (in Hz).
+    pub fn int_gen_ths_xl_config(
+        &self,
+        scale: accel::Scale,
+        odr_hz: f32,
+    ) -> Option<[Configuration; 4]> {
+        let [x, y, z] = self.int_gen_ths_xl(scale)?;
+        let config = |register: register::AG, value: u8| Configuration {
+            value,
+            sensor: Sensor::Accelerometer,
+            register: register.addr(),
+        };
+        Some([
+            config(register::AG::INT_GEN_THS_X_XL, x),
+            config(register::AG::INT_GEN_THS_Y_XL, y),
+            config(register::AG::INT_GEN_THS_Z_XL, z),
+            config(register::AG::INT_GEN_DUR_XL, self.int_gen_dur_xl(odr_hz)?),
+        ])
+    }
+}
+
+impl IntThresholdGyro {
+    /// Returns `Configuration`s to write to INT_GEN_THS_XH_G ... INT_GEN_THS_ZL_G and INT_GEN_DUR_G,
+    /// or `None` if a value cannot be represented with the given scale and output data rate.
+    pub fn int_gen_ths_g_config(
+        &self,
+        scale: gyro::Scale,
+        odr: gyro::ODR,
+    ) -> Option<[Configuration; 7]> {
+        let [xh, xl, yh, yl, zh, zl] = self.int_gen_ths_g(scale)?;
+        let config = |register: register::AG, value: u8| Configuration {
+            value,
+            sensor: Sensor::Gyro,
+            register: register.addr(),
+        };
+        Some([
+            config(register::AG::INT_GEN_THS_XH_G, xh),
+            config(register::AG::INT_GEN_THS_XL_G, xl),
+            config(register::AG::INT_GEN_THS_YH_G, yh),
+            config(register::AG::INT_GEN_THS_YL_G, yl),
+            config(register::AG::INT_GEN_THS_ZH_G, zh),
+            config(register::AG::INT_GEN_THS_ZL_G, zl),
+            config(register::AG::INT_GEN_DUR_G, self.int_gen_dur_g(odr)?),
+        ])
+    }
+}
+
+impl InactivityConfig {
+    /// Returns `Configuration`s to write to ACT_THS and ACT_DUR,
+    /// or `None` if a value cannot be represented with the given scale and output data rate (in Hz).
+    pub fn act_ths_config(&self, scale: accel::Scale, odr_hz: f32) -> Option<[Configuration; 2]> {
+        Some([
+            Configuration {
+                value: self.act_ths(scale)?,
+                sensor: Sensor::Accelerometer,
+                register: register::AG::ACT_THS.addr(),
+            },
+            Configuration {
+                value: self.act_dur(odr_hz)?,
+                sensor: Sensor::Accelerometer,
+                register: register::AG::ACT_DUR.addr(),
+            },
+        ])
+    }
+}
@@ -0,0 +1,178 @@
+//! Unified view of the interrupt and status sources of the sensors
+use crate::fifo::FIFOStatus;
+use crate::interrupts::{
+    accel_int::IntStatusAccel, gyro_int::IntStatusGyro, mag_int::IntStatusMag,
+};
+use core::ops::{BitOr, BitOrAssign};
+
+/// Compact set of events reported by `LSM9DS1::poll_events()`.
+/// The lower 7 bits follow the layout of the STATUS_REG register.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EventFlags(u16);
+
+impl EventFlags {
+    /// New accelerometer data available (XLDA)
+    pub const ACCEL_DATA_READY: Self = Self(1 << 0);
+    /// New gyroscope data available (GDA)
+    pub const GYRO_DATA_READY: Self = Self(1 << 1);
+    /// New temperature data available (TDA)
+    pub const TEMP_DATA_READY: Self = Self(1 << 2);
+    /// Boot running (BOOT_STATUS)
+    pub const BOOT_RUNNING: Self = Self(1 << 3);
+    /// Inactivity detected (INACT)
+    pub const INACTIVITY: Self = Self(1 << 4);
+    /// Gyroscope interrupt generator event (IG_G)
+    pub const GYRO_INT: Self = Self(1 << 5);
+    /// Accelerometer interrupt generator event (IG_XL)
+    pub const ACCEL_INT: Self = Self(1 << 6);
+    /// FIFO filling is equal or higher than the threshold level (FTH)
+    pub const FIFO_THRESHOLD: Self = Self(1 << 7);
+    /// FIFO is full and at least one sample has been overwritten (OVRN)
+    pub const FIFO_OVERRUN: Self = Self(1 << 8);
+    /// FIFO holds 32 unread samples (FSS5)
+    pub const FIFO_FULL: Self = Self(1 << 9);
+    /// Magnetometer interrupt event (INT)
+    pub const MAG_INT: Self = Self(1 << 10);
+
+    /// Returns an empty set
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the events of the STATUS_REG register
+    pub(crate) fn from_status_reg(value: u8) -> Self {
+        Self((value & STATUS_REG) as u16)
+    }
+
+    /// Returns the raw bits of the set
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if no event is set
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all the events of `other` are set
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if any of the events of `other` is set
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for EventFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for EventFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+/// Bitmask for the flags of the STATUS_REG register
+const STATUS_REG: u8 = 0b0111_1111;
+
+/// Events and decoded interrupt sources read at once by `LSM9DS1::poll_events()`
+#[derive(Debug)]
+pub struct Events {
+    /// Summary of all the pending events
+    pub flags: EventFlags,
+    /// Contents of the INT_GEN_SRC_XL register
+    pub accel: IntStatusAccel,
+    /// Contents of the INT_GEN_SRC_G register
+    pub gyro: IntStatusGyro,
+    /// Contents of the FIFO_SRC register
+    pub fifo: FIFOStatus,
+    /// Contents of the INT_SRC_M register, if it was read
+    pub mag: Option<IntStatusMag>,
+}
+
+impl Events {
+    /// Decodes the values of the STATUS_REG, INT_GEN_SRC_XL, INT_GEN_SRC_G, FIFO_SRC and INT_SRC_M registers
+    pub(crate) fn new(
+        status_reg: u8,
+        int_gen_src_xl: u8,
+        int_gen_src_g: u8,
+        fifo_src: u8,
+        int_src_m: Option<u8>,
+    ) -> Self {
+        let accel = IntStatusAccel::from(int_gen_src_xl);
+        let gyro = IntStatusGyro::from(int_gen_src_g);
+        let fifo = FIFOStatus::from(fifo_src);
+        let mag = int_src_m.map(IntStatusMag::from);
+
+        let mut flags = EventFlags::from_status_reg(status_reg);
+        if accel.interrupt_active {
+            flags |= EventFlags::ACCEL_INT;
+        }
+        if gyro.interrupt_active {
+            flags |= EventFlags::GYRO_INT;
+        }
+        if fifo.fifo_thresh_reached {
+            flags |= EventFlags::FIFO_THRESHOLD;
+        }
+        if fifo.fifo_overrun {
+            flags |= EventFlags::FIFO_OVERRUN;
+        }
+        if fifo.fifo_level >= 32 {
+            flags |= EventFlags::FIFO_FULL;
+        }
+        if let Some(IntStatusMag {
+            interrupt_occurs: true,
+            ..
+        }) = mag
+        {
+            flags |= EventFlags::MAG_INT;
+        }
+
+        Events {
+            flags,
+            accel,
+            gyro,
+            fifo,
+            mag,
+        }
+    }
+}
+
+#[test]
+fn decode_events() {
+    let events = Events::new(0, 0, 0, 0, None);
+    assert!(events.flags.is_empty());
+    assert!(events.mag.is_none());
+
+    let events = Events::new(0b0000_0011, 0b0100_0010, 0, 0b1010_0000, Some(0b1000_0001));
+    assert!(events.flags.contains(
+        EventFlags::ACCEL_DATA_READY
+            | EventFlags::GYRO_DATA_READY
+            | EventFlags::ACCEL_INT
+            | EventFlags::FIFO_THRESHOLD
+            | EventFlags::FIFO_FULL
+            | EventFlags::MAG_INT
+    ));
+    assert!(!events
+        .flags
+        .intersects(EventFlags::GYRO_INT | EventFlags::FIFO_OVERRUN | EventFlags::INACTIVITY));
+    assert!(events.accel.xaxis_high_event);
+    assert_eq!(events.fifo.fifo_level, 32);
+    assert!(events.mag.unwrap().xaxis_exceeds_thresh_pos);
+
+    let events = Events::new(0b0111_0000, 0, 0, 0b0100_0000, None);
+    assert_eq!(
+        events.flags,
+        EventFlags::INACTIVITY
+            | EventFlags::GYRO_INT
+            | EventFlags::ACCEL_INT
+            | EventFlags::FIFO_OVERRUN
+    );
+}
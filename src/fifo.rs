@@ -1,4 +1,12 @@
 //! Various settings related to FIFO functionality of the sensors
+use crate::{
+    accel,
+    events::EventFlags,
+    gyro,
+    interface::{Interface, Sensor},
+    Error, LSM9DS1,
+};
+
 #[allow(non_camel_case_types)]
 pub struct FIFOBitmasks;
 
@@ -10,6 +18,8 @@ impl FIFOBitmasks {
     pub(crate) const OVRN: u8 = 0b0100_0000;
     /// FIFO_SRC bits 5:0 FSS
     pub(crate) const FSS: u8 = 0b0011_1111;
+    /// CTRL_REG9 bit 4 FIFO_TEMP_EN
+    pub(crate) const FIFO_TEMP_EN: u8 = 0b0001_0000;
     /// CTRL_REG9 FIFO-related settings
     pub(crate) const CTRL_REG9_FIFO: u8 = 0b0001_0011;
     /// Decimation setting in CTRL_REG5_XL
@@ -17,7 +27,7 @@ impl FIFOBitmasks {
 }
 
 /// FIFO settings
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct FIFOConfig {
     /// FIFO memory enable
     pub fifo_enable: bool,
@@ -58,6 +68,23 @@ impl FIFOConfig {
         data
     }
 
+    /// Decodes the settings from the values of FIFO_CTRL and CTRL_REG9
+    pub(crate) fn from_registers(fifo_ctrl: u8, ctrl_reg9: u8) -> Self {
+        FIFOConfig {
+            fifo_enable: ctrl_reg9 & (1 << 1) != 0,
+            fifo_mode: match fifo_ctrl >> 5 {
+                0b001 => FIFOMode::FIFO,
+                0b011 => FIFOMode::ContinuousToFIFO,
+                0b100 => FIFOMode::BypassToContinuous,
+                0b110 => FIFOMode::Continuous,
+                _ => FIFOMode::Bypass,
+            },
+            fifo_use_threshold: ctrl_reg9 & 1 != 0,
+            fifo_threshold: (fifo_ctrl & 0b0001_1111) + 1,
+            fifo_temperature_enable: ctrl_reg9 & FIFOBitmasks::FIFO_TEMP_EN != 0,
+        }
+    }
+
     /// Returns `u8` to be written to CTRL_REG9.
     pub(crate) fn f_ctrl_reg9(&self) -> u8 {
         let mut data = 0u8;
@@ -91,13 +118,13 @@ impl From<u8> for FIFOStatus {
     fn from(value: u8) -> Self {
         let fifo_level_value = value & FIFOBitmasks::FSS;
         FIFOStatus {
-            /// Is FIFO filling equal or higher than the threshold?
+            // Is FIFO filling equal or higher than the threshold?
             fifo_thresh_reached: value & FIFOBitmasks::FTH != 0,
-            /// Is FIFO full and at least one sample has been overwritten?
+            // Is FIFO full and at least one sample has been overwritten?
             fifo_overrun: value & FIFOBitmasks::OVRN != 0,
-            /// Is FIFO empty (no unread samples)?
+            // Is FIFO empty (no unread samples)?
             fifo_empty: fifo_level_value == 0,
-            /// Read FIFO stored data level
+            // Read FIFO stored data level
             fifo_level: fifo_level_value,
         }
     }
@@ -125,6 +152,242 @@ impl FIFOMode {
     }
 }
 
+/// One FIFO frame: a gyroscope and an accelerometer sample (raw values).
+///
+/// Frame layout, in read order:
+/// - OUT_TEMP_L, OUT_TEMP_H - temperature (only if `FIFOConfig::fifo_temperature_enable` is set)
+/// - OUT_X_L_G ... OUT_Z_H_G - gyroscope
+/// - OUT_X_L_XL ... OUT_Z_H_XL - accelerometer
+///
+/// With the gyroscope powered down (see `LSM9DS1::begin_accel_only()`), frames hold accelerometer data only
+/// and `gyro` is `(0, 0, 0)`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImuFrame {
+    /// Raw gyroscope reading (x, y, z)
+    pub gyro: (i16, i16, i16),
+    /// Raw accelerometer reading (x, y, z)
+    pub accel: (i16, i16, i16),
+    /// Temperature in Celsius, if temperature data is stored in the FIFO
+    pub temperature_c: Option<f32>,
+}
+
+impl ImuFrame {
+    /// Returns the accelerometer reading in g for the given scale
+    pub fn accel_g(&self, scale: accel::Scale) -> (f32, f32, f32) {
+        let (x, y, z) = self.accel;
+        let sensitivity = scale.sensitivity();
+        (
+            x as f32 * sensitivity,
+            y as f32 * sensitivity,
+            z as f32 * sensitivity,
+        )
+    }
+
+    /// Returns the gyroscope reading in dps for the given scale
+    pub fn gyro_dps(&self, scale: gyro::Scale) -> (f32, f32, f32) {
+        let (x, y, z) = self.gyro;
+        let sensitivity = scale.sensitivity();
+        (
+            x as f32 * sensitivity,
+            y as f32 * sensitivity,
+            z as f32 * sensitivity,
+        )
+    }
+}
+
+/// A frame yielded by `FifoDrain`
+#[derive(Debug, Clone, Copy)]
+pub struct DrainedFrame {
+    /// Frame read from the FIFO
+    pub frame: ImuFrame,
+    /// The FIFO had overrun when the batch this frame belongs to was read (older samples were lost)
+    pub overrun: bool,
+}
+
+/// Iterator draining the FIFO until it is empty, see `LSM9DS1::drain_fifo()`
+pub struct FifoDrain<'a, T>
+where
+    T: Interface,
+{
+    lsm9ds1: &'a mut LSM9DS1<T>,
+    remaining: u8,
+    overrun: bool,
+    temperature: bool,
+    done: bool,
+}
+
+impl<'a, T> FifoDrain<'a, T>
+where
+    T: Interface,
+{
+    pub(crate) fn new(lsm9ds1: &'a mut LSM9DS1<T>) -> Self {
+        FifoDrain {
+            lsm9ds1,
+            remaining: 0,
+            overrun: false,
+            temperature: false,
+            done: false,
+        }
+    }
+
+    fn next_frame(&mut self) -> Result<Option<DrainedFrame>, T::Error> {
+        if self.remaining == 0 {
+            let status = self.lsm9ds1.get_fifo_status()?;
+            if status.fifo_empty {
+                self.lsm9ds1.fifo_emptied();
+                return Ok(None);
+            }
+            self.remaining = status.fifo_level;
+            self.overrun = status.fifo_overrun;
+            self.temperature = self.lsm9ds1.fifo_temperature_enabled()?;
+        }
+        self.remaining -= 1;
+        Ok(Some(DrainedFrame {
+            frame: self.lsm9ds1.read_fifo_frame(self.temperature)?,
+            overrun: self.overrun,
+        }))
+    }
+}
+
+impl<'a, T> Iterator for FifoDrain<'a, T>
+where
+    T: Interface,
+{
+    type Item = Result<DrainedFrame, T::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_frame() {
+            Ok(Some(frame)) => Some(Ok(frame)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Pre-trigger event capture in Continuous-to-FIFO mode, see `LSM9DS1::arm_event_recorder()`
+#[derive(Debug)]
+pub struct EventRecorder {
+    /// FIFO configuration used for the capture
+    fifo: FIFOConfig,
+}
+
+impl EventRecorder {
+    pub(crate) fn new(fifo: FIFOConfig) -> Self {
+        EventRecorder { fifo }
+    }
+
+    /// Checks whether the trigger fired and the FIFO is full.
+    /// If so, reads the captured frames into `frames` and returns their number; otherwise returns `None`.
+    ///
+    /// The trigger is read from STATUS_REG, so the latched interrupt stays asserted until `rearm()`.
+    pub fn poll<T>(
+        &mut self,
+        lsm9ds1: &mut LSM9DS1<T>,
+        frames: &mut [ImuFrame],
+    ) -> Result<Option<usize>, T::Error>
+    where
+        T: Interface,
+    {
+        let status = EventFlags::from_status_reg(lsm9ds1.data_available(Sensor::Accelerometer)?);
+        if !status.contains(EventFlags::ACCEL_INT) {
+            return Ok(None);
+        }
+        if lsm9ds1.get_fifo_status()?.fifo_level < 32 {
+            return Ok(None);
+        }
+        Ok(Some(lsm9ds1.read_fifo(frames)?))
+    }
+
+    /// Clears the trigger and restarts the capture (the FIFO goes through Bypass mode to be emptied)
+    pub fn rearm<T>(&mut self, lsm9ds1: &mut LSM9DS1<T>) -> Result<(), Error<T::Error>>
+    where
+        T: Interface,
+    {
+        lsm9ds1.acknowledge_accel_int()?;
+        lsm9ds1.configure_fifo(FIFOConfig {
+            fifo_mode: FIFOMode::Bypass,
+            ..self.fifo
+        })?;
+        lsm9ds1.configure_fifo(self.fifo)
+    }
+}
+
+/// Accelerometer/gyroscope interrupt pin used by the FIFO watermark
+#[derive(Debug, Clone, Copy)]
+pub enum WatermarkPin {
+    /// INT1_A/G
+    INT1,
+    /// INT2_A/G
+    INT2,
+}
+
+/// FIFO watermark handle, see `LSM9DS1::configure_fifo_watermark()`
+#[derive(Debug)]
+pub struct FifoWatermark {
+    /// Number of frames stored before the interrupt fires
+    batch_size: u8,
+    /// Pin the FIFO threshold interrupt is routed to
+    pin: WatermarkPin,
+}
+
+impl FifoWatermark {
+    pub(crate) fn new(batch_size: u8, pin: WatermarkPin) -> Self {
+        FifoWatermark { batch_size, pin }
+    }
+
+    /// Number of frames stored before the interrupt fires
+    pub fn batch_size(&self) -> u8 {
+        self.batch_size
+    }
+
+    /// Pin the FIFO threshold interrupt is routed to
+    pub fn pin(&self) -> WatermarkPin {
+        self.pin
+    }
+
+    /// Call when the watermark interrupt fires: reads the frames ready in the FIFO into `frames`
+    /// and returns their number. Frames stored while reading are left for the next interrupt.
+    pub fn on_interrupt<T>(
+        &mut self,
+        lsm9ds1: &mut LSM9DS1<T>,
+        frames: &mut [ImuFrame],
+    ) -> Result<usize, T::Error>
+    where
+        T: Interface,
+    {
+        lsm9ds1.read_fifo(frames)
+    }
+}
+
+/// What to do when the FIFO has overrun, see `LSM9DS1::recover_fifo_overrun()`
+#[derive(Debug, Clone, Copy)]
+pub enum OverrunPolicy {
+    /// Only account for the overwritten samples
+    Count,
+    /// Account for the overwritten samples, then empty the FIFO and re-apply its configuration
+    Reset,
+}
+
+/// Estimates the number of samples overwritten after an overrun (at least 1), from the time elapsed
+/// since the FIFO was last emptied, the sample period and the number of samples still stored
+pub(crate) fn overwritten_samples(elapsed_us: u64, period_us: f32, stored: usize) -> u32 {
+    if period_us <= 0.0 {
+        return 1;
+    }
+    ((elapsed_us as f32 / period_us) as u32)
+        .saturating_sub(stored as u32)
+        .max(1)
+}
+
 /// Decimation of acceleration data on OUT REG and FIFO (Refer to table 65)
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy)]
@@ -139,8 +402,142 @@ pub enum Decimate {
     _8samples = 0b11,
 }
 
+impl From<u8> for Decimate {
+    /// Decodes DEC[1:0] from the value of CTRL_REG5_XL
+    fn from(value: u8) -> Self {
+        match (value & FIFOBitmasks::DEC) >> 6 {
+            0b01 => Decimate::_2samples,
+            0b10 => Decimate::_4samples,
+            0b11 => Decimate::_8samples,
+            _ => Decimate::NoDecimation,
+        }
+    }
+}
+
 impl Decimate {
     pub fn value(self) -> u8 {
         (self as u8) << 6 // shifted to bits [7:6], can be used directly
     }
+
+    /// Returns the number of samples per update
+    pub fn factor(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// Reconstructs the sampling time of FIFO frames from the data rate and the time a batch was read
+#[derive(Debug, Clone, Copy)]
+pub struct SampleClock {
+    /// Time between two frames in microseconds
+    period_us: f32,
+    /// Timestamp of the last frame of the previous batch
+    last_us: Option<u64>,
+}
+
+impl SampleClock {
+    /// Creates a clock for frames stored at `odr_hz`, decimated by `decimation`
+    pub fn new(odr_hz: f32, decimation: Decimate) -> Self {
+        let period_us = match odr_hz {
+            x if x > 0.0 => 1_000_000.0 * decimation.factor() as f32 / x,
+            _ => 0.0,
+        };
+        SampleClock {
+            period_us,
+            last_us: None,
+        }
+    }
+
+    /// Creates a clock for frames stored at the gyroscope data rate
+    pub fn from_gyro_odr(odr: gyro::ODR, decimation: Decimate) -> Self {
+        Self::new(odr.hz(), decimation)
+    }
+
+    /// Creates a clock for frames stored at the accelerometer data rate
+    pub fn from_accel_odr(odr: accel::ODR, decimation: Decimate) -> Self {
+        Self::new(odr.hz(), decimation)
+    }
+
+    /// Time between two frames in microseconds
+    pub fn period_us(&self) -> f32 {
+        self.period_us
+    }
+
+    /// Assigns a timestamp (in microseconds) to each frame of a batch read at `read_time_us`.
+    ///
+    /// `timestamps` must have one entry per frame read. The last frame is dated at the read time
+    /// and the previous ones are evenly spaced by the sample period.
+    /// If `overrun` is set (see `FIFOStatus::fifo_overrun`), returns the estimated number of samples
+    /// lost since the previous batch (at least 1); otherwise returns 0.
+    pub fn stamp(&mut self, read_time_us: u64, overrun: bool, timestamps: &mut [u64]) -> u32 {
+        let count = timestamps.len();
+        for (i, timestamp) in timestamps.iter_mut().enumerate() {
+            let age = ((count - 1 - i) as f32 * self.period_us) as u64;
+            *timestamp = read_time_us.saturating_sub(age);
+        }
+
+        let lost = match (overrun, self.last_us) {
+            (false, _) => 0,
+            (true, Some(last_us)) => {
+                overwritten_samples(read_time_us.saturating_sub(last_us), self.period_us, count)
+            }
+            (true, None) => 1,
+        };
+
+        if count > 0 {
+            self.last_us = Some(read_time_us);
+        }
+        lost
+    }
+}
+
+#[test]
+fn fifo_config_from_registers() {
+    let config = FIFOConfig {
+        fifo_enable: true,
+        fifo_mode: FIFOMode::Continuous,
+        fifo_use_threshold: false,
+        fifo_threshold: 20,
+        fifo_temperature_enable: true,
+    };
+    let decoded = FIFOConfig::from_registers(config.f_fifo_ctrl(), config.f_ctrl_reg9());
+    assert_eq!(decoded.f_fifo_ctrl(), 0b1101_0011);
+    assert_eq!(decoded.f_ctrl_reg9(), 0b0001_0010);
+    assert_eq!(decoded.fifo_threshold, 20);
+}
+
+#[test]
+fn scale_imu_frame() {
+    let frame = ImuFrame {
+        gyro: (1000, -1000, 0),
+        accel: (16384, 0, -16384),
+        temperature_c: None,
+    };
+    assert_eq!(frame.gyro_dps(gyro::Scale::_2000DPS), (70.0, -70.0, 0.0));
+    assert_eq!(frame.accel_g(accel::Scale::_4G), (1.998848, 0.0, -1.998848));
+}
+
+#[test]
+fn stamp_fifo_batches() {
+    let mut clock = SampleClock::from_accel_odr(accel::ODR::_50Hz, Decimate::NoDecimation);
+    assert_eq!(clock.period_us(), 20_000.0);
+
+    let mut timestamps = [0u64; 4];
+    assert_eq!(clock.stamp(1_000_000, false, &mut timestamps), 0);
+    assert_eq!(timestamps, [940_000, 960_000, 980_000, 1_000_000]);
+
+    // 50 samples elapsed, 32 read
+    let mut timestamps = [0u64; 32];
+    assert_eq!(clock.stamp(2_000_000, true, &mut timestamps), 18);
+    assert_eq!(timestamps[0], 1_380_000);
+    assert_eq!(timestamps[31], 2_000_000);
+
+    let clock = SampleClock::from_gyro_odr(gyro::ODR::_238Hz, Decimate::_4samples);
+    assert_eq!(clock.period_us(), 1_000_000.0 * 4.0 / 238.0);
+}
+
+#[test]
+fn estimate_overwritten_samples() {
+    assert_eq!(overwritten_samples(1_000_000, 10_000.0, 32), 68);
+    assert_eq!(overwritten_samples(100_000, 10_000.0, 32), 1);
+    assert_eq!(overwritten_samples(1_000_000, 0.0, 32), 1);
 }
@@ -2,7 +2,7 @@
 #![allow(dead_code, non_camel_case_types)]
 
 /// Gyro settings. Use this struct to configure the sensor.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct GyroSettings {
     /// - Xen_G - X-axis output enable (false :disable, true :enable)
     pub enable_x: bool,
@@ -16,6 +16,8 @@ pub struct GyroSettings {
     pub flip_y: bool,
     /// - SignZ_G - Yaw axis (Z) angular rate sign (false: positive, true: negative)
     pub flip_z: bool,
+    /// - Orient[2:0] - Directional user orientation selection
+    pub orientation: UserOrientation,
     /// Gyroscope full-scale selection
     pub scale: Scale,
     /// Output data rate selection
@@ -32,8 +34,6 @@ pub struct GyroSettings {
     pub hpf_mode: HpFilter,
     /// HPF cutoff frequency. See page 47
     pub hpf_cutoff: HpFilterCutoff,
-    /// Latched interrupt. See page 50
-    pub latch_interrupt: LatchInterrupt,
 }
 
 impl Default for GyroSettings {
@@ -45,6 +45,7 @@ impl Default for GyroSettings {
             flip_x: false,
             flip_y: false,
             flip_z: false,
+            orientation: UserOrientation::XYZ,
             scale: Scale::_245DPS,
             sample_rate: ODR::_952Hz,
             bandwidth: Bandwidth::LPF_0,
@@ -53,12 +54,89 @@ impl Default for GyroSettings {
             low_power_mode: LowPowerMode::Disabled,
             hpf_mode: HpFilter::Disabled,
             hpf_cutoff: HpFilterCutoff::HPCF_1,
-            latch_interrupt: LatchInterrupt::Disabled,
         }
     }
 }
 
 impl GyroSettings {
+    /// Decodes the settings from the values of CTRL_REG1_G, CTRL_REG2_G, CTRL_REG3_G, CTRL_REG4 and ORIENT_CFG_G
+    pub(crate) fn from_registers(
+        ctrl_reg1_g: u8,
+        ctrl_reg2_g: u8,
+        ctrl_reg3_g: u8,
+        ctrl_reg4: u8,
+        orient_cfg_g: u8,
+    ) -> Self {
+        GyroSettings {
+            enable_x: ctrl_reg4 & (1 << 3) != 0,
+            enable_y: ctrl_reg4 & (1 << 4) != 0,
+            enable_z: ctrl_reg4 & (1 << 5) != 0,
+            flip_x: orient_cfg_g & (1 << 5) != 0,
+            flip_y: orient_cfg_g & (1 << 4) != 0,
+            flip_z: orient_cfg_g & (1 << 3) != 0,
+            orientation: match orient_cfg_g & 0b111 {
+                0b001 => UserOrientation::XZY,
+                0b010 => UserOrientation::YXZ,
+                0b011 => UserOrientation::YZX,
+                0b100 => UserOrientation::ZXY,
+                0b101 => UserOrientation::ZYX,
+                _ => UserOrientation::XYZ,
+            },
+            scale: match (ctrl_reg1_g & GyroBitmasks::FS_G) >> 3 {
+                0b01 => Scale::_500DPS,
+                0b11 => Scale::_2000DPS,
+                _ => Scale::_245DPS,
+            },
+            sample_rate: match (ctrl_reg1_g & GyroBitmasks::ODR_G) >> 5 {
+                0b001 => ODR::_14_9Hz,
+                0b010 => ODR::_59_5Hz,
+                0b011 => ODR::_119Hz,
+                0b100 => ODR::_238Hz,
+                0b101 => ODR::_476Hz,
+                0b110 => ODR::_952Hz,
+                _ => ODR::PowerDown,
+            },
+            bandwidth: match ctrl_reg1_g & GyroBitmasks::BW_G {
+                0b01 => Bandwidth::LPF_1,
+                0b10 => Bandwidth::LPF_2,
+                0b11 => Bandwidth::LPF_3,
+                _ => Bandwidth::LPF_0,
+            },
+            int_selection: match (ctrl_reg2_g >> 2) & 0b11 {
+                0b01 => GyroIntSelection::SEL_1,
+                0b10 => GyroIntSelection::SEL_2,
+                0b11 => GyroIntSelection::SEL_3,
+                _ => GyroIntSelection::SEL_0,
+            },
+            out_selection: match ctrl_reg2_g & 0b11 {
+                0b01 => GyroOutSelection::SEL_1,
+                0b10 => GyroOutSelection::SEL_2,
+                0b11 => GyroOutSelection::SEL_3,
+                _ => GyroOutSelection::SEL_0,
+            },
+            low_power_mode: match ctrl_reg3_g & GyroBitmasks::LP_MODE {
+                0 => LowPowerMode::Disabled,
+                _ => LowPowerMode::Enabled,
+            },
+            hpf_mode: match ctrl_reg3_g & GyroBitmasks::HP_EN {
+                0 => HpFilter::Disabled,
+                _ => HpFilter::Enabled,
+            },
+            hpf_cutoff: match ctrl_reg3_g & GyroBitmasks::HPCF_G {
+                0b0001 => HpFilterCutoff::HPCF_2,
+                0b0010 => HpFilterCutoff::HPCF_3,
+                0b0011 => HpFilterCutoff::HPCF_4,
+                0b0100 => HpFilterCutoff::HPCF_5,
+                0b0101 => HpFilterCutoff::HPCF_6,
+                0b0110 => HpFilterCutoff::HPCF_7,
+                0b0111 => HpFilterCutoff::HPCF_8,
+                0b1000 => HpFilterCutoff::HPCF_9,
+                0b1001 => HpFilterCutoff::HPCF_10,
+                _ => HpFilterCutoff::HPCF_1,
+            },
+        }
+    }
+
     /// Returns `u8` to write to CTRL_REG1_G. See page 45
     /// # CTRL_REG1_G: [ODR_G2][ODR_G1][ODR_G0][FS_G1][FS_G0][0][BW_G1][BW_G0]
     /// - ODR_G[2:0] - Output data rate selection
@@ -90,8 +168,7 @@ impl GyroSettings {
     /// - Zen_G - Z-axis output enable (false :disable, true :enable)
     /// - Yen_G - Y-axis output enable (false :disable, true :enable)
     /// - Xen_G - X-axis output enable (false :disable, true :enable)
-    /// - LIR_XL1 - Latched interrupt (0:not latched, 1:latched) // TODO:
-    /// - 4D_XL1 - 4D option on interrupt (0:6D used, 1:4D used) // TODO:
+    /// - LIR_XL1, 4D_XL1 - Accelerometer interrupt settings, see `IntConfigAccel`
     pub fn ctrl_reg4(&self) -> u8 {
         let mut result = 0_u8;
         if self.enable_z {
@@ -103,7 +180,7 @@ impl GyroSettings {
         if self.enable_x {
             result |= 1 << 3;
         }
-        result | self.latch_interrupt.value()
+        result
     }
 
     /// Returns `u8` to write to ORIENT_CFG_G
@@ -111,9 +188,9 @@ impl GyroSettings {
     /// - SignX_G - Pitch axis (X) angular rate sign (false: positive, true: negative)
     /// - SignY_G - Roll axis (Y) angular rate sign (false: positive, true: negative)
     /// - SignZ_G - Yaw axis (Z) angular rate sign (false: positive, true: negative)
-    /// - Orient [2:0] - Directional user orientation selection // TODO:
+    /// - Orient [2:0] - Directional user orientation selection
     pub fn orient_cfg_g(&self) -> u8 {
-        let mut result = 0_u8;
+        let mut result = self.orientation.value();
         if self.flip_x {
             result |= 1 << 5;
         }
@@ -127,6 +204,24 @@ impl GyroSettings {
     }
 }
 
+/// Bitmasks for the fields of CTRL_REG1_G and CTRL_REG3_G
+pub(crate) struct GyroBitmasks;
+
+impl GyroBitmasks {
+    /// CTRL_REG1_G ODR_G[2:0]
+    pub(crate) const ODR_G: u8 = 0b1110_0000;
+    /// CTRL_REG1_G FS_G[1:0]
+    pub(crate) const FS_G: u8 = 0b0001_1000;
+    /// CTRL_REG1_G BW_G[1:0]
+    pub(crate) const BW_G: u8 = 0b0000_0011;
+    /// CTRL_REG3_G LP_mode
+    pub(crate) const LP_MODE: u8 = 0b1000_0000;
+    /// CTRL_REG3_G HP_EN
+    pub(crate) const HP_EN: u8 = 0b0100_0000;
+    /// CTRL_REG3_G HPCF_G[3:0]
+    pub(crate) const HPCF_G: u8 = 0b0000_1111;
+}
+
 /// gyro_scale defines the possible full-scale ranges of the gyroscope:
 #[derive(Debug, Clone, Copy)]
 pub enum Scale {
@@ -177,6 +272,43 @@ impl ODR {
     pub fn value(self) -> u8 {
         (self as u8) << 5
     }
+
+    /// Returns the output data rate in Hz (0 in power-down mode)
+    pub fn hz(self) -> f32 {
+        use ODR::*;
+        match self {
+            PowerDown => 0.0,
+            _14_9Hz => 14.9,
+            _59_5Hz => 59.5,
+            _119Hz => 119.0,
+            _238Hz => 238.0,
+            _476Hz => 476.0,
+            _952Hz => 952.0,
+        }
+    }
+}
+
+/// Directional user orientation selection: which sensor axes are used as pitch, roll and yaw axes. (Refer to Table 46)
+#[derive(Debug, Clone, Copy)]
+pub enum UserOrientation {
+    /// Pitch: X, Roll: Y, Yaw: Z (000)
+    XYZ = 0b000,
+    /// Pitch: X, Roll: Z, Yaw: Y (001)
+    XZY = 0b001,
+    /// Pitch: Y, Roll: X, Yaw: Z (010)
+    YXZ = 0b010,
+    /// Pitch: Y, Roll: Z, Yaw: X (011)
+    YZX = 0b011,
+    /// Pitch: Z, Roll: X, Yaw: Y (100)
+    ZXY = 0b100,
+    /// Pitch: Z, Roll: Y, Yaw: X (101)
+    ZYX = 0b101,
+}
+
+impl UserOrientation {
+    pub fn value(self) -> u8 {
+        self as u8
+    }
 }
 
 /// Gyroscope bandwidth selection. (Refer to Table 47)
@@ -262,19 +394,6 @@ impl HpFilter {
     }
 }
 
-/// Low-power mode enable. (Refer to Table 51)
-#[derive(Debug, Clone, Copy)]
-pub enum LatchInterrupt {
-    Disabled = 0,
-    Enabled = 1,
-}
-
-impl LatchInterrupt {
-    pub fn value(self) -> u8 {
-        (self as u8) << 1
-    }
-}
-
 /// Gyroscope high-pass filter cutoff frequency selection. (Refer to Table 52)
 #[derive(Debug, Clone, Copy)]
 pub enum HpFilterCutoff {
@@ -303,6 +422,7 @@ fn gyro_init_values() {
     assert_eq!(settings.ctrl_reg2_g(), 0b0000_0000); // [0][0][0][0][INT_SEL1][INT_SEL0][OUT_SEL1][OUT_SEL0]
     assert_eq!(settings.ctrl_reg3_g(), 0b0000_0000); // [LP_mode][HP_EN][0][0][HPCF3_G][HPCF2_G][HPCF1_G][HPCF0_G]
     assert_eq!(settings.ctrl_reg4(), 0b0011_1000); // [0][0][Zen_G][Yen_G][Xen_G][0][LIR_XL1][4D_XL1]
+    assert_eq!(settings.orient_cfg_g(), 0b0000_0000); // [0][0][SignX_G][SignY_G][SignZ_G][Orient_2][Orient_1][Orient_0]
 }
 
 #[test]
@@ -406,3 +526,44 @@ fn set_gyro_bandwidth() {
     };
     assert_eq!(gyro.ctrl_reg1_g() & mask, 0b0000_0011);
 }
+
+#[test]
+fn gyro_settings_from_registers() {
+    let settings = GyroSettings {
+        enable_z: false,
+        flip_x: true,
+        flip_z: true,
+        orientation: UserOrientation::YZX,
+        scale: Scale::_500DPS,
+        sample_rate: ODR::_59_5Hz,
+        bandwidth: Bandwidth::LPF_3,
+        int_selection: GyroIntSelection::SEL_2,
+        out_selection: GyroOutSelection::SEL_1,
+        low_power_mode: LowPowerMode::Enabled,
+        hpf_mode: HpFilter::Enabled,
+        hpf_cutoff: HpFilterCutoff::HPCF_7,
+        ..Default::default()
+    };
+    let decoded = GyroSettings::from_registers(
+        settings.ctrl_reg1_g(),
+        settings.ctrl_reg2_g(),
+        settings.ctrl_reg3_g(),
+        settings.ctrl_reg4() | 0b0000_0011, // LIR_XL1, 4D_XL1
+        settings.orient_cfg_g(),
+    );
+    assert_eq!(decoded.ctrl_reg1_g(), settings.ctrl_reg1_g());
+    assert_eq!(decoded.ctrl_reg2_g(), settings.ctrl_reg2_g());
+    assert_eq!(decoded.ctrl_reg3_g(), settings.ctrl_reg3_g());
+    assert_eq!(decoded.ctrl_reg4(), settings.ctrl_reg4());
+    assert_eq!(decoded.orient_cfg_g(), settings.orient_cfg_g());
+}
+
+#[test]
+fn gyro_set_orientation() {
+    let gyro = GyroSettings {
+        flip_y: true,
+        orientation: UserOrientation::ZXY,
+        ..Default::default()
+    };
+    assert_eq!(gyro.orient_cfg_g(), 0b0001_0100);
+}
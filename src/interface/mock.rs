@@ -0,0 +1,67 @@
+//! Register map standing in for the sensor in driver tests
+use super::{Interface, Sensor};
+
+/// Maximum number of reads recorded by `MockInterface`
+const MAX_READS: usize = 32;
+
+/// Register map of the accelerometer/gyroscope and magnetometer.
+/// Multi-byte reads auto-increment the register address.
+pub(crate) struct MockInterface {
+    /// Accelerometer/gyroscope registers
+    pub ag: [u8; 0x80],
+    /// Magnetometer registers
+    pub mag: [u8; 0x80],
+    /// Accelerometer/gyroscope register ignoring writes, to simulate a write that did not stick
+    pub stuck_ag: Option<u8>,
+    /// (register address, length) of each read, in order
+    reads: [(u8, usize); MAX_READS],
+    read_count: usize,
+}
+
+impl MockInterface {
+    pub fn new() -> Self {
+        MockInterface {
+            ag: [0; 0x80],
+            mag: [0; 0x80],
+            stuck_ag: None,
+            reads: [(0, 0); MAX_READS],
+            read_count: 0,
+        }
+    }
+
+    /// Reads recorded so far
+    pub fn reads(&self) -> &[(u8, usize)] {
+        &self.reads[..self.read_count]
+    }
+
+    fn registers(&mut self, sensor: Sensor) -> &mut [u8; 0x80] {
+        match sensor {
+            Sensor::Accelerometer | Sensor::Gyro | Sensor::Temperature => &mut self.ag,
+            Sensor::Magnetometer => &mut self.mag,
+        }
+    }
+}
+
+impl Interface for MockInterface {
+    type Error = ();
+
+    fn write(&mut self, sensor: Sensor, addr: u8, value: u8) -> Result<(), Self::Error> {
+        let stuck = !matches!(sensor, Sensor::Magnetometer) && self.stuck_ag == Some(addr);
+        if !stuck {
+            self.registers(sensor)[addr as usize] = value;
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, sensor: Sensor, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        if self.read_count < MAX_READS {
+            self.reads[self.read_count] = (addr, buffer.len());
+            self.read_count += 1;
+        }
+        let registers = self.registers(sensor);
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = registers[addr as usize + i];
+        }
+        Ok(())
+    }
+}
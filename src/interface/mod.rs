@@ -3,6 +3,8 @@ pub mod spi;
 pub use self::spi::SpiInterface;
 pub mod i2c;
 pub use self::i2c::I2cInterface;
+#[cfg(test)]
+pub(crate) mod mock;
 
 /// Interface Trait. `SpiInterface` and `I2cInterface` implement this.
 pub trait Interface {
@@ -23,7 +25,7 @@ pub trait Interface {
 }
 
 /// Available Sensors to talk to
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Sensor {
     Accelerometer,
     Gyro,
@@ -1,18 +1,18 @@
 //! Functions related to accelerometer-specific interrupts
-///
-/// TODO:
-/// - set acceleration threshold for X, Y and Z axis (INT_GEN_THS_X/Y/Z_XL) in mg instead?
-/// - LIR_XL1 and 4D_XL1 bits of CTRL_REG4 => should they be incorporated in the Config struct? what's the relation between 4D_XL1 and _6D?
-///
 use super::*;
+use crate::accel;
 
 /// Accelerometer interrupt generation settings
 #[derive(Debug)]
 pub struct IntConfigAccel {
     /// Combination of accelerometer's interrupt events
     pub events_combination: Combination,
+    /// Latch interrupt request (LIR_XL1 bit of CTRL_REG4)
+    pub latch_interrupts: IntLatch,
     /// Enable 6-direction detection
     pub enable_6d: Flag,
+    /// 4D or 6D option used for position recognition (4D_XL1 bit of CTRL_REG4)
+    pub position_recognition: PosRecog,
     /// Enable interrupt generation on Z-axis high event
     pub interrupt_zaxis_high: Flag,
     /// Enable interrupt generation on Z-axis low event
@@ -31,7 +31,9 @@ impl Default for IntConfigAccel {
     fn default() -> Self {
         IntConfigAccel {
             events_combination: Combination::Or,
+            latch_interrupts: IntLatch::NotLatched,
             enable_6d: Flag::Disabled,
+            position_recognition: PosRecog::_6D,
             interrupt_zaxis_high: Flag::Disabled,
             interrupt_zaxis_low: Flag::Disabled,
             interrupt_yaxis_high: Flag::Disabled,
@@ -56,6 +58,11 @@ impl IntConfigAccel {
         data |= self.interrupt_xaxis_low.value();
         data
     }
+
+    /// Returns the accelerometer interrupt bits to be written to CTRL_REG4 (see `CfgBitmasks::CTRL_REG4_XL`)
+    pub(crate) fn ctrl_reg4(&self) -> u8 {
+        self.latch_interrupts.value() << 1 | self.position_recognition.value()
+    }
 }
 
 impl From<u8> for IntConfigAccel {
@@ -65,10 +72,14 @@ impl From<u8> for IntConfigAccel {
                 x if x > 0 => Combination::And,
                 _ => Combination::Or,
             },
+            // LIR_XL1 is in CTRL_REG4, see `LSM9DS1::get_accel_int_config()`
+            latch_interrupts: IntLatch::NotLatched,
             enable_6d: match reg_value & CfgBitmasks::_6D {
                 x if x > 0 => Flag::Enabled,
                 _ => Flag::Disabled,
             },
+            // 4D_XL1 is in CTRL_REG4, see `LSM9DS1::get_accel_int_config()`
+            position_recognition: PosRecog::_6D,
             interrupt_zaxis_high: match reg_value & CfgBitmasks::ZHIE_XL {
                 x if x > 0 => Flag::Enabled,
                 _ => Flag::Disabled,
@@ -97,6 +108,113 @@ impl From<u8> for IntConfigAccel {
     }
 }
 
+impl IntStatusAccel {
+    /// Returns the device orientation when the interrupt generator is used for position recognition
+    /// (6D and AND combination enabled, see `LSM9DS1::configure_position_recognition()`)
+    pub fn orientation(&self) -> Orientation {
+        if self.zaxis_high_event {
+            Orientation::FaceUp
+        } else if self.zaxis_low_event {
+            Orientation::FaceDown
+        } else if self.yaxis_high_event {
+            Orientation::PortraitUp
+        } else if self.yaxis_low_event {
+            Orientation::PortraitDown
+        } else if self.xaxis_high_event {
+            Orientation::LandscapeRight
+        } else if self.xaxis_low_event {
+            Orientation::LandscapeLeft
+        } else {
+            Orientation::Unknown
+        }
+    }
+}
+
+/// Device orientation recognized by the accelerometer interrupt generator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    /// Z-axis pointing up (ZH)
+    FaceUp,
+    /// Z-axis pointing down (ZL)
+    FaceDown,
+    /// Y-axis pointing up (YH)
+    PortraitUp,
+    /// Y-axis pointing down (YL)
+    PortraitDown,
+    /// X-axis pointing up (XH)
+    LandscapeRight,
+    /// X-axis pointing down (XL)
+    LandscapeLeft,
+    /// No position recognized
+    Unknown,
+}
+
+/// Accelerometer interrupt generator thresholds and duration settings
+#[derive(Debug, Clone, Copy)]
+pub struct IntThresholdAccel {
+    /// X-axis interrupt threshold in g
+    pub threshold_x: f32,
+    /// Y-axis interrupt threshold in g
+    pub threshold_y: f32,
+    /// Z-axis interrupt threshold in g
+    pub threshold_z: f32,
+    /// Minimum duration of the event before the interrupt is generated
+    pub duration: IntDuration,
+    /// Wait function (the interrupt falls only after the duration has elapsed)
+    pub wait: Flag,
+}
+
+impl Default for IntThresholdAccel {
+    fn default() -> Self {
+        IntThresholdAccel {
+            threshold_x: 0.0,
+            threshold_y: 0.0,
+            threshold_z: 0.0,
+            duration: IntDuration::Samples(0),
+            wait: Flag::Disabled,
+        }
+    }
+}
+
+impl IntThresholdAccel {
+    /// Returns values to be written to INT_GEN_THS_X_XL, INT_GEN_THS_Y_XL and INT_GEN_THS_Z_XL,
+    /// or `None` if a threshold cannot be represented with the given scale.
+    ///
+    /// The 7-bit thresholds are compared with the most significant byte of the output,
+    /// so 1 LSB is 256 times the output sensitivity.
+    pub(crate) fn int_gen_ths_xl(&self, scale: accel::Scale) -> Option<[u8; 3]> {
+        let lsb = scale.sensitivity() * 256.0;
+        let convert = |g: f32| match to_lsb(g, lsb) {
+            x if (0..=0x7F).contains(&x) => Some(x as u8),
+            _ => None,
+        };
+        Some([
+            convert(self.threshold_x)?,
+            convert(self.threshold_y)?,
+            convert(self.threshold_z)?,
+        ])
+    }
+
+    /// Returns `u8` to be written to INT_GEN_DUR_XL, or `None` if the duration cannot be represented with the given data rate.
+    /// # INT_GEN_DUR_XL: [WAIT_XL][DUR_XL6][DUR_XL5][DUR_XL4][DUR_XL3][DUR_XL2][DUR_XL1][DUR_XL0]
+    pub(crate) fn int_gen_dur_xl(&self, odr_hz: f32) -> Option<u8> {
+        let samples = self.duration.samples(odr_hz, 0x7F)?;
+        Some(self.wait.value() << 7 | samples)
+    }
+
+    /// Returns the settings with the thresholds and duration clamped to what the given scale and data rate can represent.
+    pub(crate) fn clamped(&self, scale: accel::Scale, odr_hz: f32) -> Self {
+        let max = 0x7F as f32 * scale.sensitivity() * 256.0;
+        IntThresholdAccel {
+            threshold_x: self.threshold_x.clamp(0.0, max),
+            threshold_y: self.threshold_y.clamp(0.0, max),
+            threshold_z: self.threshold_z.clamp(0.0, max),
+            duration: self.duration.clamped(odr_hz, 0x7F),
+            ..*self
+        }
+    }
+}
+
 /// Bitmasks for interrupt-related settings in INT_GEN_SRC_XL register
 pub(crate) struct InterruptBitmasks;
 
@@ -127,20 +245,43 @@ impl CfgBitmasks {
 
     pub const LIR_XL1: u8 = 0b0000_0010;
     pub const _4D_XL1: u8 = 0b0000_0001;
+    /// Accelerometer interrupt settings in CTRL_REG4
+    pub const CTRL_REG4_XL: u8 = Self::LIR_XL1 | Self::_4D_XL1;
 }
 
 #[derive(Debug)]
 /// Contents of the INT_GEN_SRC_XL register (interrupt active and differential pressure events flags)
 pub struct IntStatusAccel {
+    /// One or more interrupt events have been generated
     pub interrupt_active: bool,
+    /// X-axis high event has occurred
     pub xaxis_high_event: bool,
+    /// X-axis low event has occurred
     pub xaxis_low_event: bool,
+    /// Y-axis high event has occurred
     pub yaxis_high_event: bool,
+    /// Y-axis low event has occurred
     pub yaxis_low_event: bool,
+    /// Z-axis high event has occurred
     pub zaxis_high_event: bool,
+    /// Z-axis low event has occurred
     pub zaxis_low_event: bool,
 }
 
+impl From<u8> for IntStatusAccel {
+    fn from(value: u8) -> Self {
+        IntStatusAccel {
+            interrupt_active: value & InterruptBitmasks::IA_XL != 0,
+            xaxis_high_event: value & InterruptBitmasks::XH_XL != 0,
+            xaxis_low_event: value & InterruptBitmasks::XL_XL != 0,
+            yaxis_high_event: value & InterruptBitmasks::YH_XL != 0,
+            yaxis_low_event: value & InterruptBitmasks::YL_XL != 0,
+            zaxis_high_event: value & InterruptBitmasks::ZH_XL != 0,
+            zaxis_low_event: value & InterruptBitmasks::ZL_XL != 0,
+        }
+    }
+}
+
 #[test]
 fn configure_accel_int() {
     let config = IntConfigAccel::default();
@@ -148,7 +289,9 @@ fn configure_accel_int() {
 
     let config = IntConfigAccel {
         events_combination: Combination::And,
+        latch_interrupts: IntLatch::Latched,
         enable_6d: Flag::Enabled,
+        position_recognition: PosRecog::_4D,
         interrupt_zaxis_high: Flag::Enabled,
         interrupt_zaxis_low: Flag::Enabled,
         interrupt_yaxis_high: Flag::Enabled,
@@ -157,6 +300,7 @@ fn configure_accel_int() {
         interrupt_xaxis_low: Flag::Enabled,
     };
     assert_eq!(config.int_gen_cfg_xl(), 0b1111_1111);
+    assert_eq!(config.ctrl_reg4(), 0b0000_0011);
 
     let config = IntConfigAccel {
         interrupt_zaxis_high: Flag::Enabled,
@@ -165,3 +309,93 @@ fn configure_accel_int() {
     };
     assert_eq!(config.int_gen_cfg_xl(), 0b0010_0001);
 }
+
+#[test]
+fn accel_int_status() {
+    let status = IntStatusAccel::from(0b0000_0000);
+    assert!(!status.interrupt_active);
+    assert!(!status.xaxis_low_event);
+
+    let status = IntStatusAccel::from(0b0110_0001);
+    assert!(status.interrupt_active);
+    assert!(status.zaxis_high_event);
+    assert!(!status.zaxis_low_event);
+    assert!(!status.yaxis_high_event);
+    assert!(!status.yaxis_low_event);
+    assert!(!status.xaxis_high_event);
+    assert!(status.xaxis_low_event);
+}
+
+#[test]
+fn accel_int_threshold() {
+    let config = IntThresholdAccel::default();
+    assert_eq!(config.int_gen_ths_xl(accel::Scale::_2G), Some([0, 0, 0]));
+    assert_eq!(config.int_gen_dur_xl(accel::ODR::PowerDown.hz()), Some(0));
+
+    let config = IntThresholdAccel {
+        threshold_x: 0.5,
+        threshold_y: 1.0,
+        threshold_z: 4.5,
+        duration: IntDuration::Milliseconds(100.0),
+        wait: Flag::Enabled,
+    };
+    assert_eq!(config.int_gen_ths_xl(accel::Scale::_2G), None);
+    assert_eq!(config.int_gen_ths_xl(accel::Scale::_4G), None);
+    assert_eq!(config.int_gen_ths_xl(accel::Scale::_8G), Some([8, 16, 72]));
+    let clamped = config.clamped(accel::Scale::_2G, accel::ODR::_952Hz.hz());
+    assert_eq!(
+        clamped.int_gen_ths_xl(accel::Scale::_2G),
+        Some([32, 64, 0x7F])
+    );
+    assert_eq!(
+        config.int_gen_dur_xl(accel::ODR::_119Hz.hz()),
+        Some(0b1000_1100)
+    );
+    assert_eq!(
+        config.int_gen_dur_xl(accel::ODR::_952Hz.hz()),
+        Some(0b1101_1111)
+    );
+
+    let config = IntThresholdAccel {
+        threshold_x: -0.5,
+        duration: IntDuration::Milliseconds(200.0),
+        ..Default::default()
+    };
+    assert_eq!(config.int_gen_ths_xl(accel::Scale::_2G), None);
+    assert_eq!(config.int_gen_dur_xl(accel::ODR::_952Hz.hz()), None);
+    let clamped = config.clamped(accel::Scale::_2G, accel::ODR::_952Hz.hz());
+    assert_eq!(clamped.int_gen_ths_xl(accel::Scale::_2G), Some([0, 0, 0]));
+    assert_eq!(clamped.int_gen_dur_xl(accel::ODR::_952Hz.hz()), Some(0x7F));
+}
+
+#[test]
+fn accel_orientation() {
+    assert_eq!(
+        IntStatusAccel::from(0b0000_0000).orientation(),
+        Orientation::Unknown
+    );
+    assert_eq!(
+        IntStatusAccel::from(0b0110_0000).orientation(),
+        Orientation::FaceUp
+    );
+    assert_eq!(
+        IntStatusAccel::from(0b0101_0000).orientation(),
+        Orientation::FaceDown
+    );
+    assert_eq!(
+        IntStatusAccel::from(0b0100_1000).orientation(),
+        Orientation::PortraitUp
+    );
+    assert_eq!(
+        IntStatusAccel::from(0b0100_0100).orientation(),
+        Orientation::PortraitDown
+    );
+    assert_eq!(
+        IntStatusAccel::from(0b0100_0010).orientation(),
+        Orientation::LandscapeRight
+    );
+    assert_eq!(
+        IntStatusAccel::from(0b0100_0001).orientation(),
+        Orientation::LandscapeLeft
+    );
+}
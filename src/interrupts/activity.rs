@@ -0,0 +1,102 @@
+//! Functions related to inactivity detection and gyroscope sleep-on-inactivity
+use super::*;
+use crate::accel;
+
+/// Inactivity detection settings
+#[derive(Debug, Clone, Copy)]
+pub struct InactivityConfig {
+    /// Inactivity threshold in g
+    pub threshold: f32,
+    /// Time below the threshold before the device is considered inactive
+    pub duration: IntDuration,
+    /// Gyroscope operating mode while the device is inactive
+    pub gyro_mode: GyroInactiveMode,
+}
+
+impl Default for InactivityConfig {
+    fn default() -> Self {
+        InactivityConfig {
+            threshold: 0.0,
+            duration: IntDuration::Samples(0),
+            gyro_mode: GyroInactiveMode::PowerDown,
+        }
+    }
+}
+
+impl InactivityConfig {
+    /// Returns `u8` to be written to ACT_THS, or `None` if the threshold cannot be represented with the given scale.
+    /// # ACT_THS: [SLEEP_ON_INACT_EN][ACT_THS6][ACT_THS5][ACT_THS4][ACT_THS3][ACT_THS2][ACT_THS1][ACT_THS0]
+    ///
+    /// As for the interrupt generator, 1 LSB of the threshold is 256 times the output sensitivity.
+    pub(crate) fn act_ths(&self, scale: accel::Scale) -> Option<u8> {
+        match to_lsb(self.threshold, scale.sensitivity() * 256.0) {
+            x if (0..=0x7F).contains(&x) => Some(self.gyro_mode.value() | x as u8),
+            _ => None,
+        }
+    }
+
+    /// Returns `u8` to be written to ACT_DUR, or `None` if the duration cannot be represented with the given data rate.
+    pub(crate) fn act_dur(&self, odr_hz: f32) -> Option<u8> {
+        self.duration.samples(odr_hz, 0xFF)
+    }
+
+    /// Returns the settings with the threshold and duration clamped to what the given scale and data rate can represent.
+    pub(crate) fn clamped(&self, scale: accel::Scale, odr_hz: f32) -> Self {
+        InactivityConfig {
+            threshold: self
+                .threshold
+                .clamp(0.0, 0x7F as f32 * scale.sensitivity() * 256.0),
+            duration: self.duration.clamped(odr_hz, 0xFF),
+            ..*self
+        }
+    }
+}
+
+/// Gyroscope operating mode selection when inactivity is detected (SLEEP_ON_INACT_EN)
+#[derive(Debug, Clone, Copy)]
+pub enum GyroInactiveMode {
+    /// Gyroscope in power-down
+    PowerDown = 0,
+    /// Gyroscope in sleep mode
+    Sleep = 1,
+}
+
+impl GyroInactiveMode {
+    pub fn value(self) -> u8 {
+        (self as u8) << 7
+    }
+}
+
+/// Bitmasks for activity-related flags in STATUS_REG register
+pub(crate) struct ActivityBitmasks;
+
+impl ActivityBitmasks {
+    pub const INACT: u8 = 0b0001_0000;
+}
+
+#[test]
+fn configure_inactivity() {
+    let config = InactivityConfig::default();
+    assert_eq!(config.act_ths(accel::Scale::_2G), Some(0b0000_0000));
+    assert_eq!(config.act_dur(accel::ODR::PowerDown.hz()), Some(0));
+
+    let config = InactivityConfig {
+        threshold: 0.25,
+        duration: IntDuration::Milliseconds(2000.0),
+        gyro_mode: GyroInactiveMode::Sleep,
+    };
+    assert_eq!(config.act_ths(accel::Scale::_2G), Some(0b1001_0000));
+    assert_eq!(config.act_ths(accel::Scale::_16G), Some(0b1000_0001));
+    assert_eq!(config.act_dur(accel::ODR::_119Hz.hz()), Some(238));
+    assert_eq!(config.act_dur(accel::ODR::_238Hz.hz()), None);
+    let clamped = config.clamped(accel::Scale::_2G, accel::ODR::_238Hz.hz());
+    assert_eq!(clamped.act_dur(accel::ODR::_238Hz.hz()), Some(0xFF));
+
+    let config = InactivityConfig {
+        threshold: 2.5,
+        ..Default::default()
+    };
+    assert_eq!(config.act_ths(accel::Scale::_2G), None);
+    let clamped = config.clamped(accel::Scale::_2G, 0.0);
+    assert_eq!(clamped.act_ths(accel::Scale::_2G), Some(0x7F));
+}
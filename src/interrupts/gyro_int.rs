@@ -1,10 +1,6 @@
 //! Functions related to gyroscope-specific interrupts
-///
-/// TODO:
-/// - complete gyroscope threshold setting for X, Y and Z axis (INT_GEN_THS_X/Y/Z_G)
-/// - ORIENT_CFG_G settings (user orientation selection (???)) -> to be done in gyro.rs
-///
 use super::*;
+use crate::gyro;
 
 /// Gyroscope interrupt generator settings
 #[derive(Debug)]
@@ -97,6 +93,81 @@ impl From<u8> for IntConfigGyro {
     }
 }
 
+/// Gyroscope interrupt generator thresholds, counter mode and duration settings
+#[derive(Debug, Clone, Copy)]
+pub struct IntThresholdGyro {
+    /// Pitch axis (X) interrupt threshold in dps
+    pub threshold_x: f32,
+    /// Roll axis (Y) interrupt threshold in dps
+    pub threshold_y: f32,
+    /// Yaw axis (Z) interrupt threshold in dps
+    pub threshold_z: f32,
+    /// Decrement or reset counter mode
+    pub counter: Counter,
+    /// Minimum duration of the event before the interrupt is generated
+    pub duration: IntDuration,
+    /// Wait function (the interrupt falls only after the duration has elapsed)
+    pub wait: Flag,
+}
+
+impl Default for IntThresholdGyro {
+    fn default() -> Self {
+        IntThresholdGyro {
+            threshold_x: 0.0,
+            threshold_y: 0.0,
+            threshold_z: 0.0,
+            counter: Counter::Reset,
+            duration: IntDuration::Samples(0),
+            wait: Flag::Disabled,
+        }
+    }
+}
+
+impl IntThresholdGyro {
+    /// Returns values to be written to INT_GEN_THS_XH_G, INT_GEN_THS_XL_G, INT_GEN_THS_YH_G,
+    /// INT_GEN_THS_YL_G, INT_GEN_THS_ZH_G and INT_GEN_THS_ZL_G,
+    /// or `None` if a threshold cannot be represented with the given scale.
+    /// # INT_GEN_THS_XH_G: [DCRM_G][THS_G_X14]...[THS_G_X8]
+    ///
+    /// The thresholds are 15-bit words in two's complement with the same sensitivity as the output.
+    pub(crate) fn int_gen_ths_g(&self, scale: gyro::Scale) -> Option<[u8; 6]> {
+        let convert = |dps: f32| match to_lsb(dps, scale.sensitivity()) {
+            x if (-0x4000..0x4000).contains(&x) => Some(x as u16 & 0x7FFF),
+            _ => None,
+        };
+        let x = convert(self.threshold_x)?;
+        let y = convert(self.threshold_y)?;
+        let z = convert(self.threshold_z)?;
+        Some([
+            self.counter.value() << 7 | (x >> 8) as u8,
+            x as u8,
+            (y >> 8) as u8,
+            y as u8,
+            (z >> 8) as u8,
+            z as u8,
+        ])
+    }
+
+    /// Returns `u8` to be written to INT_GEN_DUR_G, or `None` if the duration cannot be represented with the given data rate.
+    /// # INT_GEN_DUR_G: [WAIT_G][DUR_G6][DUR_G5][DUR_G4][DUR_G3][DUR_G2][DUR_G1][DUR_G0]
+    pub(crate) fn int_gen_dur_g(&self, odr: gyro::ODR) -> Option<u8> {
+        let samples = self.duration.samples(odr.hz(), 0x7F)?;
+        Some(self.wait.value() << 7 | samples)
+    }
+
+    /// Returns the settings with the thresholds and duration clamped to what the given scale and data rate can represent.
+    pub(crate) fn clamped(&self, scale: gyro::Scale, odr: gyro::ODR) -> Self {
+        let max = 0x3FFF as f32 * scale.sensitivity();
+        IntThresholdGyro {
+            threshold_x: self.threshold_x.clamp(-max, max),
+            threshold_y: self.threshold_y.clamp(-max, max),
+            threshold_z: self.threshold_z.clamp(-max, max),
+            duration: self.duration.clamped(odr.hz(), 0x7F),
+            ..*self
+        }
+    }
+}
+
 /// Bitmasks for interrupt-related settings in INT_GEN_SRC_G register
 pub(crate) struct InterruptBitmasks;
 
@@ -129,15 +200,36 @@ impl CfgBitmasks {
 #[derive(Debug)]
 /// Contents of the INT_GEN_SRC_G register (interrupt active and differential pressure events Flags)
 pub struct IntStatusGyro {
+    /// One or more interrupt events have been generated (IA_G)
     pub interrupt_active: bool,
+    /// Pitch (X) high event has occurred
     pub xaxis_high_event: bool,
+    /// Pitch (X) low event has occurred
     pub xaxis_low_event: bool,
+    /// Roll (Y) high event has occurred
     pub yaxis_high_event: bool,
+    /// Roll (Y) low event has occurred
     pub yaxis_low_event: bool,
+    /// Yaw (Z) high event has occurred
     pub zaxis_high_event: bool,
+    /// Yaw (Z) low event has occurred
     pub zaxis_low_event: bool,
 }
 
+impl From<u8> for IntStatusGyro {
+    fn from(value: u8) -> Self {
+        IntStatusGyro {
+            interrupt_active: value & InterruptBitmasks::IA_G != 0,
+            xaxis_high_event: value & InterruptBitmasks::XH_G != 0,
+            xaxis_low_event: value & InterruptBitmasks::XL_G != 0,
+            yaxis_high_event: value & InterruptBitmasks::YH_G != 0,
+            yaxis_low_event: value & InterruptBitmasks::YL_G != 0,
+            zaxis_high_event: value & InterruptBitmasks::ZH_G != 0,
+            zaxis_low_event: value & InterruptBitmasks::ZL_G != 0,
+        }
+    }
+}
+
 #[test]
 fn configure_gyro_int() {
     let config = IntConfigGyro::default();
@@ -155,3 +247,52 @@ fn configure_gyro_int() {
     };
     assert_eq!(config.int_gen_cfg_g(), 0b1111_1111);
 }
+
+#[test]
+fn gyro_int_status() {
+    let status = IntStatusGyro::from(0b0000_0000);
+    assert!(!status.interrupt_active);
+
+    let status = IntStatusGyro::from(0b0100_1100);
+    assert!(status.interrupt_active);
+    assert!(!status.zaxis_high_event);
+    assert!(!status.zaxis_low_event);
+    assert!(status.yaxis_high_event);
+    assert!(status.yaxis_low_event);
+    assert!(!status.xaxis_high_event);
+    assert!(!status.xaxis_low_event);
+}
+
+#[test]
+fn gyro_int_threshold() {
+    let config = IntThresholdGyro::default();
+    assert_eq!(config.int_gen_ths_g(gyro::Scale::_245DPS), Some([0; 6]));
+    assert_eq!(config.int_gen_dur_g(gyro::ODR::PowerDown), Some(0));
+
+    let config = IntThresholdGyro {
+        threshold_x: 70.0,
+        threshold_y: -0.0175,
+        threshold_z: 200.0,
+        counter: Counter::Decrement,
+        duration: IntDuration::Samples(10),
+        wait: Flag::Enabled,
+    };
+    assert_eq!(config.int_gen_ths_g(gyro::Scale::_245DPS), None);
+    assert_eq!(
+        config.int_gen_ths_g(gyro::Scale::_500DPS),
+        Some([
+            0b1000_1111,
+            0b1010_0000,
+            0x7F,
+            0xFF,
+            0b0010_1100,
+            0b1010_0101
+        ])
+    );
+    assert_eq!(config.int_gen_dur_g(gyro::ODR::_952Hz), Some(0b1000_1010));
+    let clamped = config.clamped(gyro::Scale::_245DPS, gyro::ODR::_952Hz);
+    assert_eq!(
+        clamped.int_gen_ths_g(gyro::Scale::_245DPS),
+        Some([0b1001_1111, 0x40, 0x7F, 0xFE, 0x3F, 0xFF])
+    );
+}
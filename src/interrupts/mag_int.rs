@@ -1,5 +1,6 @@
 //! Functions related to magnetometer-specific interrupts
 use super::*;
+use crate::mag;
 
 /// Magnetometer interrupt pin (INT_CFG_M) settings
 #[derive(Debug)]
@@ -82,6 +83,18 @@ impl From<u8> for IntConfigMag {
     }
 }
 
+/// Returns values to be written to INT_THS_L_M and INT_THS_H_M for a threshold in gauss,
+/// or `None` if the threshold cannot be represented with the given scale.
+/// # INT_THS_H_M: [0][THS14][THS13][THS12][THS11][THS10][THS9][THS8]
+///
+/// The threshold is an absolute value with the same sensitivity as the output.
+pub(crate) fn int_ths_m(threshold: f32, scale: mag::Scale) -> Option<[u8; 2]> {
+    match to_lsb(threshold * 1000.0, scale.sensitivity()) {
+        x if (0..=0x7FFF).contains(&x) => Some([x as u8, (x >> 8) as u8]),
+        _ => None,
+    }
+}
+
 /// Bitmasks for interrupt-related settings in INT_SRC_M register
 pub(crate) struct InterruptBitmasks;
 
@@ -113,16 +126,39 @@ impl CfgBitmasks {
 #[derive(Debug)]
 /// Contents of the INT_SRC_M register (interrupt active and threshold excess events Flags)
 pub struct IntStatusMag {
+    /// X-axis value exceeds the threshold on the positive side
     pub xaxis_exceeds_thresh_pos: bool,
+    /// Y-axis value exceeds the threshold on the positive side
     pub yaxis_exceeds_thresh_pos: bool,
+    /// Z-axis value exceeds the threshold on the positive side
     pub zaxis_exceeds_thresh_pos: bool,
+    /// X-axis value exceeds the threshold on the negative side
     pub xaxis_exceeds_thresh_neg: bool,
+    /// Y-axis value exceeds the threshold on the negative side
     pub yaxis_exceeds_thresh_neg: bool,
+    /// Z-axis value exceeds the threshold on the negative side
     pub zaxis_exceeds_thresh_neg: bool,
+    /// Internal measurement range overflow on magnetic value (MROI)
     pub measurement_range_overflow: bool,
+    /// An interrupt event occurred (INT)
     pub interrupt_occurs: bool,
 }
 
+impl From<u8> for IntStatusMag {
+    fn from(value: u8) -> Self {
+        IntStatusMag {
+            xaxis_exceeds_thresh_pos: value & InterruptBitmasks::PTH_X != 0,
+            yaxis_exceeds_thresh_pos: value & InterruptBitmasks::PTH_Y != 0,
+            zaxis_exceeds_thresh_pos: value & InterruptBitmasks::PTH_Z != 0,
+            xaxis_exceeds_thresh_neg: value & InterruptBitmasks::NTH_X != 0,
+            yaxis_exceeds_thresh_neg: value & InterruptBitmasks::NTH_Y != 0,
+            zaxis_exceeds_thresh_neg: value & InterruptBitmasks::NTH_Z != 0,
+            measurement_range_overflow: value & InterruptBitmasks::MROI != 0,
+            interrupt_occurs: value & InterruptBitmasks::INT != 0,
+        }
+    }
+}
+
 #[test]
 fn configure_mag_int() {
     let config = IntConfigMag::default();
@@ -138,3 +174,28 @@ fn configure_mag_int() {
     };
     assert_eq!(config.int_cfg_m(), 0b1110_0101);
 }
+
+#[test]
+fn mag_int_status() {
+    let status = IntStatusMag::from(0b0000_0000);
+    assert!(!status.interrupt_occurs);
+
+    let status = IntStatusMag::from(0b1000_1011);
+    assert!(status.xaxis_exceeds_thresh_pos);
+    assert!(!status.yaxis_exceeds_thresh_pos);
+    assert!(!status.zaxis_exceeds_thresh_pos);
+    assert!(!status.xaxis_exceeds_thresh_neg);
+    assert!(status.yaxis_exceeds_thresh_neg);
+    assert!(!status.zaxis_exceeds_thresh_neg);
+    assert!(status.measurement_range_overflow);
+    assert!(status.interrupt_occurs);
+}
+
+#[test]
+fn mag_int_threshold() {
+    assert_eq!(int_ths_m(0.0, mag::Scale::_4G), Some([0x00, 0x00]));
+    assert_eq!(int_ths_m(0.5, mag::Scale::_4G), Some([0xF3, 0x0D]));
+    assert_eq!(int_ths_m(0.5, mag::Scale::_16G), Some([0x5E, 0x03]));
+    assert_eq!(int_ths_m(8.0, mag::Scale::_4G), None);
+    assert_eq!(int_ths_m(-0.5, mag::Scale::_4G), None);
+}
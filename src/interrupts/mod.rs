@@ -1,6 +1,7 @@
 //! Enums used by various interrupt-related functions
 
 pub mod accel_int;
+pub mod activity;
 pub mod gyro_int;
 pub mod mag_int;
 pub mod pins_config;
@@ -100,6 +101,36 @@ impl Counter {
     }
 }
 
+/// Duration of an interrupt event, either as a number of samples or in milliseconds
+#[derive(Debug, Clone, Copy)]
+pub enum IntDuration {
+    /// Number of samples at the sensor's output data rate
+    Samples(u8),
+    /// Milliseconds, converted with the sensor's output data rate
+    Milliseconds(f32),
+}
+
+impl IntDuration {
+    /// Returns the duration as a number of samples at `odr_hz`, or `None` if it doesn't fit in `max`
+    pub(crate) fn samples(self, odr_hz: f32, max: u8) -> Option<u8> {
+        match self {
+            IntDuration::Samples(x) if x <= max => Some(x),
+            IntDuration::Samples(_) => None,
+            IntDuration::Milliseconds(ms) => to_samples(ms, odr_hz, max),
+        }
+    }
+    /// Returns the duration clamped to `max` samples at `odr_hz`
+    pub(crate) fn clamped(self, odr_hz: f32, max: u8) -> Self {
+        match self {
+            IntDuration::Samples(x) => IntDuration::Samples(x.min(max)),
+            IntDuration::Milliseconds(ms) if odr_hz > 0.0 => {
+                IntDuration::Milliseconds(ms.clamp(0.0, max as f32 * 1000.0 / odr_hz))
+            }
+            duration => duration,
+        }
+    }
+}
+
 /// Settings for various interrupt-related flags, Enabled or Disabled
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy)]
@@ -137,3 +168,45 @@ impl Combination {
         }
     }
 }
+
+/// Converts `value` into a whole number of `lsb` steps, rounded to the nearest integer
+pub(crate) fn to_lsb(value: f32, lsb: f32) -> i32 {
+    let steps = value / lsb;
+    if steps < 0.0 {
+        (steps - 0.5) as i32
+    } else {
+        (steps + 0.5) as i32
+    }
+}
+
+/// Converts a duration in milliseconds into a number of samples at `odr_hz`.
+/// Returns `None` if the result doesn't fit in `max` or the sensor is powered down.
+pub(crate) fn to_samples(duration_ms: f32, odr_hz: f32, max: u8) -> Option<u8> {
+    if duration_ms == 0.0 {
+        return Some(0);
+    }
+    if odr_hz == 0.0 {
+        return None;
+    }
+    match to_lsb(duration_ms * odr_hz, 1000.0) {
+        x if x < 0 || x > max as i32 => None,
+        x => Some(x as u8),
+    }
+}
+
+#[test]
+fn lsb_conversion() {
+    assert_eq!(to_lsb(1.0, 0.25), 4);
+    assert_eq!(to_lsb(0.6, 0.25), 2);
+    assert_eq!(to_lsb(-0.6, 0.25), -2);
+    assert_eq!(to_samples(0.0, 0.0, 127), Some(0));
+    assert_eq!(to_samples(10.0, 0.0, 127), None);
+    assert_eq!(to_samples(100.0, 119.0, 127), Some(12));
+    assert_eq!(to_samples(2000.0, 119.0, 127), None);
+    assert_eq!(IntDuration::Samples(127).samples(0.0, 127), Some(127));
+    assert_eq!(IntDuration::Samples(128).samples(0.0, 127), None);
+    assert_eq!(
+        IntDuration::Milliseconds(50.0).samples(238.0, 127),
+        Some(12)
+    );
+}
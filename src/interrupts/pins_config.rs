@@ -165,6 +165,8 @@ impl IntConfigAG1Bitmask {
 #[derive(Debug)]
 pub struct IntConfigAG2 {
     // --- INT2_CTRL REGISTER ---
+    /// Enable inactivity interrupt on pin INT2_A/G
+    pub enable_inactivity: Flag,
     /// Enable FSS5 interrupt on on pin INT1_A/G
     pub enable_fss5: Flag,
     /// Enable overrun interrupt on on pin INT2_A/G
@@ -182,6 +184,7 @@ pub struct IntConfigAG2 {
 impl Default for IntConfigAG2 {
     fn default() -> Self {
         IntConfigAG2 {
+            enable_inactivity: Flag::Disabled,
             enable_fss5: Flag::Disabled,
             enable_overrun: Flag::Disabled,
             enable_fth: Flag::Disabled,
@@ -195,6 +198,10 @@ impl Default for IntConfigAG2 {
 impl From<u8> for IntConfigAG2 {
     fn from(value: u8) -> Self {
         IntConfigAG2 {
+            enable_inactivity: match value & IntConfigAG2Bitmask::INT2_INACT {
+                x if x > 0 => Flag::Enabled,
+                _ => Flag::Disabled,
+            },
             enable_fss5: match value & IntConfigAG2Bitmask::INT2_FSS5 {
                 x if x > 0 => Flag::Enabled,
                 _ => Flag::Disabled,
@@ -227,6 +234,7 @@ impl IntConfigAG2 {
     /// Returns `u8` to be written to INT2_CTRL register
     pub(crate) fn int2_ctrl(&self) -> u8 {
         let mut data: u8 = 0;
+        data |= self.enable_inactivity.value() << 7;
         data |= self.enable_fss5.value() << 5;
         data |= self.enable_overrun.value() << 4;
         data |= self.enable_fth.value() << 3;
@@ -274,6 +282,7 @@ fn configure_ag2() {
     assert_eq!(config.int2_ctrl(), 0b0000_0000);
 
     let config = IntConfigAG2 {
+        enable_inactivity: Flag::Enabled,
         enable_fss5: Flag::Enabled,
         enable_overrun: Flag::Enabled,
         enable_fth: Flag::Enabled,
@@ -281,7 +290,7 @@ fn configure_ag2() {
         enable_gyro_dataready: Flag::Enabled,
         enable_accel_dataready: Flag::Enabled,
     };
-    assert_eq!(config.int2_ctrl(), 0b0011_1111);
+    assert_eq!(config.int2_ctrl(), 0b1011_1111);
 }
 
 #[test]
@@ -7,6 +7,7 @@
 // #![deny(warnings, missing_docs)]
 pub mod accel;
 pub mod configuration;
+pub mod events;
 pub mod fifo;
 pub mod gyro;
 pub mod interface;
@@ -14,16 +15,22 @@ pub mod interrupts;
 pub mod mag;
 pub mod register;
 
-use accel::AccelSettings;
-use configuration::ConfigToWrite;
-use fifo::{Decimate, FIFOBitmasks, FIFOConfig, FIFOStatus};
-use gyro::GyroSettings;
+use accel::{AccelBitmasks, AccelSettings};
+use configuration::{ConfigToWrite, Configuration};
+use events::Events;
+use fifo::{
+    Decimate, EventRecorder, FIFOBitmasks, FIFOConfig, FIFOMode, FIFOStatus, FifoDrain,
+    FifoWatermark, ImuFrame, OverrunPolicy, SampleClock, WatermarkPin,
+};
+use gyro::{GyroBitmasks, GyroSettings};
 use interface::{Interface, Sensor};
-use interrupts::accel_int::IntConfigAccel;
-use interrupts::gyro_int::IntConfigGyro;
-use interrupts::mag_int::IntConfigMag;
+use interrupts::accel_int::{self, IntConfigAccel, IntStatusAccel, IntThresholdAccel, Orientation};
+use interrupts::activity::{ActivityBitmasks, InactivityConfig};
+use interrupts::gyro_int::{IntConfigGyro, IntStatusGyro, IntThresholdGyro};
+use interrupts::mag_int::{self, IntConfigMag, IntStatusMag};
 use interrupts::pins_config::{self, IntConfigAG1, IntConfigAG2, PinConfig};
-use mag::MagSettings;
+use interrupts::{Combination, Flag, IntLatch, PosRecog};
+use mag::{MagBitmasks, MagSettings};
 use pins_config::PinConfigBitmask;
 
 /// Accelerometer/Gyroscope's ID
@@ -35,6 +42,32 @@ const TEMP_SCALE: f32 = 16.0;
 /// The output of the temperature sensor is 0 (typ.) at 25 °C. see page 14: Temperature sensor characteristics
 const TEMP_BIAS: f32 = 25.0;
 
+/// Errors returned by the driver
+#[derive(Debug, PartialEq)]
+pub enum Error<E> {
+    /// Interface error
+    Comm(E),
+    /// A setting cannot be represented in the sensor's registers with the current configuration
+    OutOfRange,
+    /// A register read back with write verification enabled does not hold the written value
+    Verify {
+        /// Sensor the register belongs to
+        sensor: Sensor,
+        /// Register address
+        register: u8,
+        /// Value written
+        expected: u8,
+        /// Value read back
+        found: u8,
+    },
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(error: E) -> Self {
+        Error::Comm(error)
+    }
+}
+
 /// LSM9DS1 init struct.
 /// Use this struct to configure sensors and init LSM9DS1 with an interface of your choice.
 #[derive(Default)]
@@ -58,6 +91,15 @@ impl LSM9DS1Init {
             accel: self.accel,
             gyro: self.gyro,
             mag: self.mag,
+            mag_int_threshold: None,
+            fifo: None,
+            accel_int_threshold: None,
+            inactivity: None,
+            gyro_int_threshold: None,
+            fifo_dropped: 0,
+            fifo_overrun_counted: 0,
+            decimation: Decimate::NoDecimation,
+            verify_writes: false,
         }
     }
 }
@@ -71,6 +113,24 @@ where
     accel: AccelSettings,
     gyro: GyroSettings,
     mag: MagSettings,
+    /// Magnetometer interrupt threshold in gauss, re-applied when the scale changes
+    mag_int_threshold: Option<f32>,
+    /// Accelerometer interrupt thresholds, re-applied when the scale or data rate changes
+    accel_int_threshold: Option<IntThresholdAccel>,
+    /// Inactivity detection settings, re-applied when the scale or data rate changes
+    inactivity: Option<InactivityConfig>,
+    /// Gyro interrupt thresholds, re-applied when the scale or data rate changes
+    gyro_int_threshold: Option<IntThresholdGyro>,
+    /// Last FIFO configuration written by `configure_fifo()`
+    fifo: Option<FIFOConfig>,
+    /// Running count of FIFO samples lost to overruns
+    fifo_dropped: u32,
+    /// Samples already counted for the current overrun, until the FIFO is emptied
+    fifo_overrun_counted: u32,
+    /// Decimation set with `set_decimation()`
+    decimation: Decimate,
+    /// Read back every register written by the driver
+    verify_writes: bool,
 }
 
 impl<T> LSM9DS1<T>
@@ -78,10 +138,8 @@ where
     T: Interface,
 {
     /// Write a configuration to a register.
-    fn write_register<C: ConfigToWrite>(&mut self, config: C) -> Result<(), T::Error> {
-        self.interface
-            .write(config.sensor(), config.addr(), config.byte())?;
-        Ok(())
+    fn write_register<C: ConfigToWrite>(&mut self, config: C) -> Result<(), Error<T::Error>> {
+        self.write_byte(config.sensor(), config.addr(), config.byte())
     }
     /// Modify a register with a configuration.
     fn modify_register<C: ConfigToWrite>(
@@ -89,12 +147,37 @@ where
         config: C,
         original_value: u8,
         bitmask: u8,
-    ) -> Result<(), T::Error> {
+    ) -> Result<(), Error<T::Error>> {
         let mut data: u8 = original_value & bitmask;
         data |= config.byte();
-        self.interface.write(config.sensor(), config.addr(), data)?;
+        self.write_byte(config.sensor(), config.addr(), data)
+    }
+    /// Write a byte to a register, and read it back if write verification is enabled.
+    fn write_byte(
+        &mut self,
+        sensor: Sensor,
+        address: u8,
+        value: u8,
+    ) -> Result<(), Error<T::Error>> {
+        self.interface.write(sensor, address, value)?;
+        if self.verify_writes {
+            let found = self.read_register(sensor, address)?;
+            if found != value {
+                return Err(Error::Verify {
+                    sensor,
+                    register: address,
+                    expected: value,
+                    found,
+                });
+            }
+        }
         Ok(())
     }
+    /// Enable or disable write verification: every register written by the driver is read back,
+    /// and `Error::Verify` is returned if it does not hold the written value.
+    pub fn set_verify_writes(&mut self, verify: bool) {
+        self.verify_writes = verify;
+    }
 
     fn reachable(&mut self, sensor: Sensor) -> Result<bool, T::Error> {
         use Sensor::*;
@@ -113,32 +196,324 @@ where
     pub fn mag_is_reacheable(&mut self) -> Result<bool, T::Error> {
         self.reachable(Sensor::Magnetometer)
     }
-    /// Initializes Accelerometer with sensor settings.
-    pub fn begin_accel(&mut self) -> Result<(), T::Error> {
+    /// Initializes Accelerometer with sensor settings and re-applies the thresholds set with
+    /// `configure_accel_int_threshold()` and `configure_inactivity()` (see `set_accel_scale()`).
+    pub fn begin_accel(&mut self) -> Result<(), Error<T::Error>> {
         self.write_register(self.accel.ctrl_reg5_xl_config())?;
         self.write_register(self.accel.ctrl_reg6_xl_config())?;
         self.write_register(self.accel.ctrl_reg7_xl_config())?;
+        // CTRL_REG5_XL is written without decimation
+        self.decimation = Decimate::NoDecimation;
+        self.restore_thresholds()
+    }
+    /// Sets the Accel full-scale selection, keeping the other CTRL_REG6_XL settings, and re-applies the thresholds
+    /// set with `configure_accel_int_threshold()` and `configure_inactivity()`.
+    /// The scale is applied even if `Error::OutOfRange` is returned because a threshold cannot be represented anymore;
+    /// that threshold is then clamped to the full scale.
+    pub fn set_accel_scale(&mut self, scale: accel::Scale) -> Result<(), Error<T::Error>> {
+        let ctrl_reg6 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG6_XL.addr())?;
+        self.modify_register(scale, ctrl_reg6, !AccelBitmasks::FS_XL)?;
+        self.accel.scale = scale;
+        self.restore_thresholds()
+    }
+    /// Sets the Accel output data rate, keeping the other CTRL_REG6_XL settings, and re-applies the thresholds
+    /// like `set_accel_scale()`. With the Gyro on, the accelerometer keeps running at the gyroscope data rate.
+    pub fn set_accel_odr(&mut self, odr: accel::ODR) -> Result<(), Error<T::Error>> {
+        let ctrl_reg6 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG6_XL.addr())?;
+        self.modify_register(odr, ctrl_reg6, !AccelBitmasks::ODR_XL)?;
+        self.accel.sample_rate = odr;
+        self.restore_thresholds()
+    }
+    /// Re-applies the stored interrupt and inactivity thresholds for the current scales and data rates.
+    /// Returns `Error::OutOfRange` if one of them cannot be represented anymore; it is then clamped.
+    /// Thresholds of a powered-down sensor are left as they are and re-applied when it is powered up.
+    fn restore_thresholds(&mut self) -> Result<(), Error<T::Error>> {
+        let mut restored = true;
+        let (scale, odr_hz) = (self.accel.scale, self.accel_odr_hz());
+        let accel_on = odr_hz > 0.0;
+        if let Some(config) = self.accel_int_threshold.filter(|_| accel_on) {
+            restored &= self.write_clamped(
+                config.int_gen_ths_xl_config(scale, odr_hz),
+                config
+                    .clamped(scale, odr_hz)
+                    .int_gen_ths_xl_config(scale, odr_hz),
+            )?;
+        }
+        if let Some(config) = self.inactivity.filter(|_| accel_on) {
+            restored &= self.write_clamped(
+                config.act_ths_config(scale, odr_hz),
+                config.clamped(scale, odr_hz).act_ths_config(scale, odr_hz),
+            )?;
+        }
+        let (scale, odr) = (self.gyro.scale, self.gyro.sample_rate);
+        let gyro_on = !self.is_accel_only();
+        if let Some(config) = self.gyro_int_threshold.filter(|_| gyro_on) {
+            restored &= self.write_clamped(
+                config.int_gen_ths_g_config(scale, odr),
+                config.clamped(scale, odr).int_gen_ths_g_config(scale, odr),
+            )?;
+        }
+        if restored {
+            Ok(())
+        } else {
+            Err(Error::OutOfRange)
+        }
+    }
+    /// Sets the Accel anti-aliasing filter bandwidth, keeping the other CTRL_REG6_XL settings.
+    /// `bandwidth` is used only with `BandwidthSelection::ByBW`.
+    pub fn set_accel_bandwidth(
+        &mut self,
+        selection: accel::BandwidthSelection,
+        bandwidth: accel::Bandwidth,
+    ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg6 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG6_XL.addr())?;
+        let settings = AccelSettings {
+            bandwidth_selection: selection,
+            bandwidth,
+            ..self.accel
+        };
+        self.modify_register(
+            settings.bandwidth_config(),
+            ctrl_reg6,
+            !(AccelBitmasks::BW_SCAL_ODR | AccelBitmasks::BW_XL),
+        )?;
+        self.accel = settings;
         Ok(())
     }
+    /// Initializes Accel with sensor settings and powers the Gyro down.
+    /// The accelerometer then runs at its own data rate and FIFO frames hold accelerometer data only.
+    pub fn begin_accel_only(&mut self) -> Result<(), Error<T::Error>> {
+        self.gyro.sample_rate = gyro::ODR::PowerDown;
+        self.write_register(self.gyro.ctrl_reg1_g_config())?;
+        self.begin_accel()
+    }
+    /// Is the Gyro powered down (accelerometer-only operation)?
+    pub fn is_accel_only(&self) -> bool {
+        matches!(self.gyro.sample_rate, gyro::ODR::PowerDown)
+    }
+    /// Effective accelerometer data rate in Hz: with the Gyro on, the accelerometer runs at the gyroscope data rate.
+    pub fn accel_odr_hz(&self) -> f32 {
+        if self.is_accel_only() {
+            self.accel.sample_rate.hz()
+        } else {
+            self.gyro.sample_rate.hz()
+        }
+    }
     /// Initializes Gyro with sensor settings.
-    pub fn begin_gyro(&mut self) -> Result<(), T::Error> {
+    /// The stored thresholds are re-applied (see `set_gyro_scale()`).
+    pub fn begin_gyro(&mut self) -> Result<(), Error<T::Error>> {
         self.write_register(self.gyro.ctrl_reg1_g_config())?;
         self.write_register(self.gyro.ctrl_reg2_g_config())?;
         self.write_register(self.gyro.ctrl_reg3_g_config())?;
-        self.write_register(self.gyro.ctrl_reg4_config())?;
+        self.write_register(self.gyro.orient_cfg_g_config())?;
+        // CTRL_REG4 also holds accelerometer interrupt settings
+        let ctrl_reg4 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG4.addr())?;
+        self.modify_register(
+            self.gyro.ctrl_reg4_config(),
+            ctrl_reg4,
+            accel_int::CfgBitmasks::CTRL_REG4_XL,
+        )?;
+        self.restore_thresholds()
+    }
+    /// Sets the Gyro full-scale selection, keeping the other CTRL_REG1_G settings, and re-applies the thresholds
+    /// set with `configure_gyro_int_threshold()`.
+    /// The scale is applied even if `Error::OutOfRange` is returned because a threshold cannot be represented anymore;
+    /// that threshold is then clamped to the full scale.
+    pub fn set_gyro_scale(&mut self, scale: gyro::Scale) -> Result<(), Error<T::Error>> {
+        let ctrl_reg1 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG1_G.addr())?;
+        self.modify_register(scale, ctrl_reg1, !GyroBitmasks::FS_G)?;
+        self.gyro.scale = scale;
+        self.restore_thresholds()
+    }
+    /// Sets the Gyro output data rate, keeping the other CTRL_REG1_G settings, and re-applies the thresholds
+    /// like `set_gyro_scale()`. The accelerometer runs at this data rate too, so its thresholds are re-applied as well.
+    /// `ODR::PowerDown` turns the Gyro off (accelerometer-only operation); its thresholds are re-applied when it is powered up.
+    pub fn set_gyro_odr(&mut self, odr: gyro::ODR) -> Result<(), Error<T::Error>> {
+        let ctrl_reg1 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG1_G.addr())?;
+        self.modify_register(odr, ctrl_reg1, !GyroBitmasks::ODR_G)?;
+        self.gyro.sample_rate = odr;
+        self.restore_thresholds()
+    }
+    /// Sets the Gyro bandwidth, keeping the other CTRL_REG1_G settings
+    pub fn set_gyro_bandwidth(
+        &mut self,
+        bandwidth: gyro::Bandwidth,
+    ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg1 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG1_G.addr())?;
+        self.modify_register(bandwidth, ctrl_reg1, !GyroBitmasks::BW_G)?;
+        self.gyro.bandwidth = bandwidth;
+        Ok(())
+    }
+    /// Enables or disables the Gyro high-pass filter and sets its cutoff frequency, keeping the other CTRL_REG3_G settings
+    pub fn set_gyro_hpf(
+        &mut self,
+        mode: gyro::HpFilter,
+        cutoff: gyro::HpFilterCutoff,
+    ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg3 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG3_G.addr())?;
+        let settings = GyroSettings {
+            hpf_mode: mode,
+            hpf_cutoff: cutoff,
+            ..self.gyro
+        };
+        self.modify_register(
+            settings.hpf_config(),
+            ctrl_reg3,
+            !(GyroBitmasks::HP_EN | GyroBitmasks::HPCF_G),
+        )?;
+        self.gyro = settings;
+        Ok(())
+    }
+    /// Sets the Gyro low-power mode, keeping the other CTRL_REG3_G settings
+    pub fn set_gyro_low_power(&mut self, mode: gyro::LowPowerMode) -> Result<(), Error<T::Error>> {
+        let ctrl_reg3 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG3_G.addr())?;
+        self.modify_register(mode, ctrl_reg3, !GyroBitmasks::LP_MODE)?;
+        self.gyro.low_power_mode = mode;
         Ok(())
     }
     /// Initializes Magnetometer with sensor settings.
-    pub fn begin_mag(&mut self) -> Result<(), T::Error> {
+    /// Returns `Error::OutOfRange` if the threshold set with `set_mag_int_threshold()` had to be clamped to the new scale.
+    pub fn begin_mag(&mut self) -> Result<(), Error<T::Error>> {
         self.write_register(self.mag.ctrl_reg1_m_config())?;
         self.write_register(self.mag.ctrl_reg2_m_config())?;
         self.write_register(self.mag.ctrl_reg3_m_config())?;
         self.write_register(self.mag.ctrl_reg4_m_config())?;
         self.write_register(self.mag.ctrl_reg5_m_config())?;
+        self.restore_mag_int_threshold()
+    }
+    /// Re-applies the stored Magnetometer interrupt threshold for the current scale.
+    /// A threshold beyond the full scale is clamped to the maximum and `Error::OutOfRange` is returned.
+    fn restore_mag_int_threshold(&mut self) -> Result<(), Error<T::Error>> {
+        if let Some(threshold) = self.mag_int_threshold {
+            match mag_int::int_ths_m(threshold, self.mag.scale) {
+                Some(bytes) => self.write_mag_int_threshold(bytes)?,
+                None => {
+                    self.write_mag_int_threshold([0xFF, 0x7F])?;
+                    return Err(Error::OutOfRange);
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Sets the Magnetometer full-scale selection and re-applies the interrupt threshold set with `set_mag_int_threshold()`.
+    /// The scale is applied even if `Error::OutOfRange` is returned because the threshold had to be clamped.
+    pub fn set_mag_scale(&mut self, scale: mag::Scale) -> Result<(), Error<T::Error>> {
+        let ctrl_reg2 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG2_M.addr())?;
+        self.modify_register(scale, ctrl_reg2, !MagBitmasks::FS)?;
+        self.mag.scale = scale;
+        self.restore_mag_int_threshold()
+    }
+    /// Sets the Magnetometer output data rate, keeping the other CTRL_REG1_M settings
+    pub fn set_mag_odr(&mut self, odr: mag::ODR) -> Result<(), Error<T::Error>> {
+        let ctrl_reg1 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG1_M.addr())?;
+        self.modify_register(odr, ctrl_reg1, !MagBitmasks::DO)?;
+        self.mag.sample_rate = odr;
+        Ok(())
+    }
+    /// Sets the Magnetometer X and Y axes operative mode, keeping the other CTRL_REG1_M settings
+    pub fn set_mag_xy_mode(&mut self, mode: mag::OpModeXY) -> Result<(), Error<T::Error>> {
+        let ctrl_reg1 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG1_M.addr())?;
+        self.modify_register(mode, ctrl_reg1, !MagBitmasks::OM)?;
+        self.mag.x_y_performance = mode;
+        Ok(())
+    }
+    /// Sets the Magnetometer Z axis operative mode, keeping the other CTRL_REG4_M settings
+    pub fn set_mag_z_mode(&mut self, mode: mag::OpModeZ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg4 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG4_M.addr())?;
+        self.modify_register(mode, ctrl_reg4, !MagBitmasks::OMZ)?;
+        self.mag.z_performance = mode;
+        Ok(())
+    }
+    /// Sets the Magnetometer operating mode (continuous, single conversion, power-down), keeping the other CTRL_REG3_M settings
+    pub fn set_mag_system_op(&mut self, mode: mag::SysOpMode) -> Result<(), Error<T::Error>> {
+        let ctrl_reg3 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG3_M.addr())?;
+        self.modify_register(mode, ctrl_reg3, !MagBitmasks::MD)?;
+        self.mag.system_op = mode;
         Ok(())
     }
+    /// Enables or disables the Magnetometer temperature compensation, keeping the other CTRL_REG1_M settings
+    pub fn set_mag_temp_compensation(
+        &mut self,
+        mode: mag::TempComp,
+    ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg1 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG1_M.addr())?;
+        self.modify_register(mode, ctrl_reg1, !MagBitmasks::TEMP_COMP)?;
+        self.mag.temp_compensation = mode;
+        Ok(())
+    }
+    /// Enables or disables the Magnetometer fast read, keeping the other CTRL_REG5_M settings
+    pub fn set_mag_fast_read(&mut self, mode: mag::FastRead) -> Result<(), Error<T::Error>> {
+        let ctrl_reg5 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG5_M.addr())?;
+        self.modify_register(mode, ctrl_reg5, !MagBitmasks::FAST_READ)?;
+        self.mag.fast_read = mode;
+        Ok(())
+    }
+    /// Sets the Magnetometer block data update, keeping the other CTRL_REG5_M settings
+    pub fn set_mag_block_data_update(
+        &mut self,
+        mode: mag::BlockDataUpdate,
+    ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg5 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG5_M.addr())?;
+        self.modify_register(mode, ctrl_reg5, !MagBitmasks::BDU)?;
+        self.mag.block_data_update = mode;
+        Ok(())
+    }
+
+    /// Reads the Accel settings back from CTRL_REG5_XL, CTRL_REG6_XL and CTRL_REG7_XL.
+    /// The driver's settings are updated to match the device.
+    pub fn read_accel_settings(&mut self) -> Result<AccelSettings, T::Error> {
+        let ctrl_reg5 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG5_XL.addr())?;
+        let ctrl_reg6 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG6_XL.addr())?;
+        let ctrl_reg7 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG7_XL.addr())?;
+        self.accel = AccelSettings::from_registers(ctrl_reg5, ctrl_reg6, ctrl_reg7);
+        self.decimation = ctrl_reg5.into();
+        Ok(self.accel)
+    }
+    /// Reads the Gyro settings back from CTRL_REG1_G ... CTRL_REG4 and ORIENT_CFG_G.
+    /// The driver's settings are updated to match the device.
+    pub fn read_gyro_settings(&mut self) -> Result<GyroSettings, T::Error> {
+        let ctrl_reg1 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG1_G.addr())?;
+        let ctrl_reg2 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG2_G.addr())?;
+        let ctrl_reg3 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG3_G.addr())?;
+        let ctrl_reg4 = self.read_register(Sensor::Gyro, register::AG::CTRL_REG4.addr())?;
+        let orient_cfg = self.read_register(Sensor::Gyro, register::AG::ORIENT_CFG_G.addr())?;
+        self.gyro =
+            GyroSettings::from_registers(ctrl_reg1, ctrl_reg2, ctrl_reg3, ctrl_reg4, orient_cfg);
+        Ok(self.gyro)
+    }
+    /// Reads the Magnetometer settings back from CTRL_REG1_M ... CTRL_REG5_M.
+    /// The driver's settings are updated to match the device.
+    pub fn read_mag_settings(&mut self) -> Result<MagSettings, T::Error> {
+        let ctrl_reg1 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG1_M.addr())?;
+        let ctrl_reg2 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG2_M.addr())?;
+        let ctrl_reg3 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG3_M.addr())?;
+        let ctrl_reg4 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG4_M.addr())?;
+        let ctrl_reg5 =
+            self.read_register(Sensor::Magnetometer, register::Mag::CTRL_REG5_M.addr())?;
+        self.mag =
+            MagSettings::from_registers(ctrl_reg1, ctrl_reg2, ctrl_reg3, ctrl_reg4, ctrl_reg5);
+        Ok(self.mag)
+    }
 
-    fn data_available(&mut self, sensor: Sensor) -> Result<u8, T::Error> {
+    pub(crate) fn data_available(&mut self, sensor: Sensor) -> Result<u8, T::Error> {
         use Sensor::*;
         let register = match sensor {
             Accelerometer | Gyro | Temperature => register::AG::STATUS_REG_1.addr(),
@@ -178,10 +553,7 @@ where
     fn read_sensor_raw(&mut self, sensor: Sensor, addr: u8) -> Result<(i16, i16, i16), T::Error> {
         let mut bytes = [0u8; 6];
         self.interface.read(sensor, addr, &mut bytes)?;
-        let x: i16 = (bytes[1] as i16) << 8 | bytes[0] as i16;
-        let y: i16 = (bytes[3] as i16) << 8 | bytes[2] as i16;
-        let z: i16 = (bytes[5] as i16) << 8 | bytes[4] as i16;
-        Ok((x, y, z))
+        Ok(to_xyz(&bytes))
     }
     /// raw accelerometer readings
     pub fn read_accel_raw(&mut self) -> Result<(i16, i16, i16), T::Error> {
@@ -215,6 +587,31 @@ where
     pub fn read_mag_raw(&mut self) -> Result<(i16, i16, i16), T::Error> {
         self.read_sensor_raw(Sensor::Magnetometer, register::Mag::OUT_X_L_M.addr())
     }
+    /// raw magnetometer readings with reduced resolution (`MagSettings::fast_read` enabled):
+    /// only the high bytes are read, the low bytes are 0.
+    /// Falls back to `read_mag_raw()` when fast read is disabled.
+    pub fn read_mag_fast_raw(&mut self) -> Result<(i16, i16, i16), T::Error> {
+        if let mag::FastRead::Disabled = self.mag.fast_read {
+            return self.read_mag_raw();
+        }
+        let mut bytes = [0u8; 3];
+        self.interface.read(
+            Sensor::Magnetometer,
+            register::Mag::OUT_X_H_M.addr(),
+            &mut bytes,
+        )?;
+        Ok(to_xyz_high(&bytes))
+    }
+    /// calculated magnetometer readings (x, y, z) with reduced resolution, see `read_mag_fast_raw()`
+    pub fn read_mag_fast(&mut self) -> Result<(f32, f32, f32), T::Error> {
+        let (x, y, z) = self.read_mag_fast_raw()?;
+        let sensitivity = self.mag.scale.sensitivity();
+        Ok((
+            x as f32 * sensitivity,
+            y as f32 * sensitivity,
+            z as f32 * sensitivity,
+        ))
+    }
     /// calculated magnetometer readings (x, y, z)
     pub fn read_mag(&mut self) -> Result<(f32, f32, f32), T::Error> {
         let (x, y, z) = self.read_mag_raw()?;
@@ -225,6 +622,13 @@ where
             z as f32 * sensitivity,
         ))
     }
+    /// Sees if the device is inactive (INACT flag of the STATUS_REG register)
+    pub fn is_inactive(&mut self) -> Result<bool, T::Error> {
+        match self.data_available(Sensor::Accelerometer)? {
+            x if x & ActivityBitmasks::INACT > 0 => Ok(true),
+            _ => Ok(false),
+        }
+    }
     /// Reads calculated temperature in Celsius
     pub fn read_temp(&mut self) -> Result<f32, T::Error> {
         let mut bytes = [0u8; 2];
@@ -234,11 +638,11 @@ where
             &mut bytes,
         )?;
         let result: i16 = (bytes[1] as i16) << 8 | bytes[0] as i16;
-        Ok((result as f32) / TEMP_SCALE + TEMP_BIAS)
+        Ok(to_celsius(result))
     }
 
     /// Enable and configure FIFO
-    pub fn configure_fifo(&mut self, config: FIFOConfig) -> Result<(), T::Error> {
+    pub fn configure_fifo(&mut self, config: FIFOConfig) -> Result<(), Error<T::Error>> {
         // write values to the FIFO_CTRL register
         self.write_register(config.f_fifo_ctrl_config())?;
 
@@ -249,7 +653,21 @@ where
             config.f_ctrl_reg9_config(),
             ctrl_reg9,
             !FIFOBitmasks::CTRL_REG9_FIFO,
-        )
+        )?;
+        self.fifo = Some(config);
+        Ok(())
+    }
+
+    /// Reads the FIFO settings back from FIFO_CTRL and CTRL_REG9.
+    /// The driver's FIFO configuration is updated to match the device.
+    pub fn read_fifo_config(&mut self) -> Result<FIFOConfig, T::Error> {
+        let fifo_ctrl =
+            self.read_register(Sensor::Accelerometer, register::AG::FIFO_CTRL.addr())?;
+        let ctrl_reg9 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG9.addr())?;
+        let config = FIFOConfig::from_registers(fifo_ctrl, ctrl_reg9);
+        self.fifo = Some(config);
+        Ok(config)
     }
 
     /// Get flags and FIFO level from the FIFO_STATUS register
@@ -259,25 +677,250 @@ where
             .into())
     }
 
+    /// Read the frames stored in the FIFO into `frames` (raw values).
+    ///
+    /// Reads `fifo_level` frames, or fewer if `frames` is shorter, and returns the number of frames read.
+    /// Use `ImuFrame::accel_g()` and `ImuFrame::gyro_dps()` with `accel_scale()` and `gyro_scale()` to get scaled values.
+    pub fn read_fifo(&mut self, frames: &mut [ImuFrame]) -> Result<usize, T::Error> {
+        let level = self.get_fifo_status()?.fifo_level as usize;
+        let count = level.min(frames.len());
+        if count == level {
+            self.fifo_emptied();
+        }
+        let temperature = self.fifo_temperature_enabled()?;
+        for frame in frames[..count].iter_mut() {
+            *frame = self.read_fifo_frame(temperature)?;
+        }
+        Ok(count)
+    }
+
+    /// Read one frame from the FIFO (see `ImuFrame` for the layout).
+    /// The gyroscope and accelerometer output registers are not contiguous, so a frame takes two reads.
+    /// The FIFO advances to the next frame once the accelerometer output has been read.
+    /// `temperature` tells whether temperature data is stored in the FIFO (see `fifo_temperature_enabled()`).
+    pub(crate) fn read_fifo_frame(&mut self, temperature: bool) -> Result<ImuFrame, T::Error> {
+        if self.is_accel_only() {
+            let temperature_c = if temperature {
+                Some(self.read_temp()?)
+            } else {
+                None
+            };
+            Ok(ImuFrame {
+                gyro: (0, 0, 0),
+                accel: self.read_accel_raw()?,
+                temperature_c,
+            })
+        } else if temperature {
+            // OUT_TEMP_L, OUT_TEMP_H, STATUS_REG, then the gyroscope output registers
+            let mut bytes = [0u8; 9];
+            self.interface
+                .read(Sensor::Gyro, register::AG::OUT_TEMP_L.addr(), &mut bytes)?;
+            let temperature: i16 = (bytes[1] as i16) << 8 | bytes[0] as i16;
+            Ok(ImuFrame {
+                gyro: to_xyz(&bytes[3..]),
+                accel: self.read_accel_raw()?,
+                temperature_c: Some(to_celsius(temperature)),
+            })
+        } else {
+            Ok(ImuFrame {
+                gyro: self.read_gyro_raw()?,
+                accel: self.read_accel_raw()?,
+                temperature_c: None,
+            })
+        }
+    }
+
+    /// Is temperature data stored in the FIFO? (FIFO_TEMP_EN bit of CTRL_REG9)
+    pub(crate) fn fifo_temperature_enabled(&mut self) -> Result<bool, T::Error> {
+        let ctrl_reg9 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG9.addr())?;
+        Ok(ctrl_reg9 & FIFOBitmasks::FIFO_TEMP_EN != 0)
+    }
+
+    /// Returns an iterator yielding the frames stored in the FIFO one by one.
+    ///
+    /// The FIFO level is checked again after each batch, and the iteration stops once the FIFO is empty
+    /// or after an interface error. Each frame reports whether its batch was read after an overrun.
+    pub fn drain_fifo(&mut self) -> FifoDrain<'_, T> {
+        FifoDrain::new(self)
+    }
+
+    /// Empty the FIFO by switching to Bypass mode, then re-apply the last configuration written by `configure_fifo()`.
+    /// Without one (e.g. after an MCU reset), the configuration is read back from the device first.
+    pub fn reset_fifo(&mut self) -> Result<(), Error<T::Error>> {
+        let config = match self.fifo {
+            Some(config) => config,
+            None => self.read_fifo_config()?,
+        };
+        self.configure_fifo(FIFOConfig {
+            fifo_mode: FIFOMode::Bypass,
+            ..config
+        })?;
+        self.configure_fifo(config)?;
+        self.fifo_emptied();
+        Ok(())
+    }
+
+    /// Check the FIFO for an overrun and recover from it according to `policy`.
+    ///
+    /// `elapsed_us` is the time since the FIFO was last emptied, used to estimate how many samples
+    /// were overwritten (at least 1). The estimate is added to `fifo_dropped_samples()` and returned;
+    /// returns 0 if the FIFO has not overrun. Called again before the FIFO is emptied
+    /// (`OverrunPolicy::Count`), only the samples lost since the previous call are added.
+    pub fn recover_fifo_overrun(
+        &mut self,
+        policy: OverrunPolicy,
+        elapsed_us: u64,
+    ) -> Result<u32, Error<T::Error>> {
+        let status = self.get_fifo_status()?;
+        if !status.fifo_overrun {
+            return Ok(0);
+        }
+        let period_us = self.fifo_sample_clock().period_us();
+        let overwritten =
+            fifo::overwritten_samples(elapsed_us, period_us, status.fifo_level as usize);
+        let dropped = overwritten.saturating_sub(self.fifo_overrun_counted);
+        self.fifo_overrun_counted = self.fifo_overrun_counted.max(overwritten);
+        self.fifo_dropped = self.fifo_dropped.saturating_add(dropped);
+        if let OverrunPolicy::Reset = policy {
+            self.reset_fifo()?;
+        }
+        Ok(dropped)
+    }
+
+    /// Starts a new overrun accounting once the FIFO has been emptied
+    pub(crate) fn fifo_emptied(&mut self) {
+        self.fifo_overrun_counted = 0;
+    }
+
+    /// Number of FIFO samples lost to overruns, as accounted by `recover_fifo_overrun()`
+    pub fn fifo_dropped_samples(&self) -> u32 {
+        self.fifo_dropped
+    }
+
+    /// Reset the count of FIFO samples lost to overruns
+    pub fn clear_fifo_dropped_samples(&mut self) {
+        self.fifo_dropped = 0;
+    }
+
+    /// Returns a `SampleClock` to timestamp FIFO frames with the current data rate and the decimation
+    /// set with `set_decimation()` (or read back with `read_accel_settings()`).
+    ///
+    /// With the gyroscope on, frames are stored at the gyroscope data rate, otherwise at the accelerometer data rate.
+    pub fn fifo_sample_clock(&self) -> SampleClock {
+        SampleClock::new(self.accel_odr_hz(), self.decimation)
+    }
+
+    /// Arm the event recorder: the FIFO runs in Continuous mode until the Accelerometer interrupt
+    /// generator fires, then switches to FIFO mode and keeps the frames captured before and around the event.
+    ///
+    /// `trigger` and `threshold` configure the interrupt generator; the interrupt is latched and routed to INT1_A/G.
+    /// Use `EventRecorder::poll()` to get the 32 captured frames.
+    pub fn arm_event_recorder(
+        &mut self,
+        trigger: IntConfigAccel,
+        threshold: IntThresholdAccel,
+    ) -> Result<EventRecorder, Error<T::Error>> {
+        self.configure_accel_int_threshold(threshold)?;
+        self.configure_interrupts_accel(IntConfigAccel {
+            latch_interrupts: IntLatch::Latched,
+            ..trigger
+        })?;
+        let ag1 = self.get_ag1_config()?;
+        self.configure_interrupts_ag1(IntConfigAG1 {
+            enable_accel_int: Flag::Enabled,
+            ..ag1
+        })?;
+
+        let mut recorder = EventRecorder::new(FIFOConfig {
+            fifo_enable: true,
+            fifo_mode: FIFOMode::ContinuousToFIFO,
+            fifo_use_threshold: false,
+            fifo_threshold: 32,
+            fifo_temperature_enable: self.fifo_temperature_enabled()?,
+        });
+        recorder.rearm(self)?;
+        Ok(recorder)
+    }
+
+    /// Stream the FIFO in batches: the FIFO runs in Continuous mode and the FIFO threshold interrupt
+    /// is routed to `pin` once `batch_size` frames (1 to 32) are stored. `pins` sets the pins' active level and mode.
+    ///
+    /// Call `FifoWatermark::on_interrupt()` from the interrupt to read the ready frames.
+    pub fn configure_fifo_watermark(
+        &mut self,
+        batch_size: u8,
+        pin: WatermarkPin,
+        pins: PinConfig,
+    ) -> Result<FifoWatermark, Error<T::Error>> {
+        if !(1..=32).contains(&batch_size) {
+            return Err(Error::OutOfRange);
+        }
+        let fifo_temperature_enable = self.fifo_temperature_enabled()?;
+        self.configure_fifo(FIFOConfig {
+            fifo_enable: true,
+            fifo_mode: FIFOMode::Continuous,
+            fifo_use_threshold: false,
+            fifo_threshold: batch_size,
+            fifo_temperature_enable,
+        })?;
+        match pin {
+            WatermarkPin::INT1 => {
+                let ag1 = self.get_ag1_config()?;
+                self.configure_interrupts_ag1(IntConfigAG1 {
+                    enable_fth: Flag::Enabled,
+                    ..ag1
+                })
+            }
+            WatermarkPin::INT2 => {
+                let ag2 = self.get_ag2_config()?;
+                self.configure_interrupts_ag2(IntConfigAG2 {
+                    enable_fth: Flag::Enabled,
+                    ..ag2
+                })
+            }
+        }?;
+        self.configure_interrupts_pins(pins)?;
+        Ok(FifoWatermark::new(batch_size, pin))
+    }
+
+    /// Current accelerometer full-scale selection
+    pub fn accel_scale(&self) -> accel::Scale {
+        self.accel.scale
+    }
+
+    /// Current gyroscope full-scale selection
+    pub fn gyro_scale(&self) -> gyro::Scale {
+        self.gyro.scale
+    }
+
     /// Sets decimation of acceleration data on OUT REG and FIFO
-    pub fn set_decimation(&mut self, decimation: Decimate) -> Result<(), T::Error> {
+    pub fn set_decimation(&mut self, decimation: Decimate) -> Result<(), Error<T::Error>> {
         let ctrl_reg5 =
             self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG5_XL.addr())?;
-        self.modify_register(decimation, ctrl_reg5, !FIFOBitmasks::DEC)
+        self.modify_register(decimation, ctrl_reg5, !FIFOBitmasks::DEC)?;
+        self.decimation = decimation;
+        Ok(())
     }
 
     /// Enable interrupts for accelerometer/gyroscope and configure the INT1_A/G interrupt pin
-    pub fn configure_interrupts_ag1(&mut self, config: IntConfigAG1) -> Result<(), T::Error> {
+    pub fn configure_interrupts_ag1(
+        &mut self,
+        config: IntConfigAG1,
+    ) -> Result<(), Error<T::Error>> {
         self.write_register(config)
     }
 
     /// Enable interrupts for accelerometer/gyroscope and configure the INT2_A/G interrupt pin
-    pub fn configure_interrupts_ag2(&mut self, config: IntConfigAG2) -> Result<(), T::Error> {
+    pub fn configure_interrupts_ag2(
+        &mut self,
+        config: IntConfigAG2,
+    ) -> Result<(), Error<T::Error>> {
         self.write_register(config)
     }
 
     /// Interrupt pins electrical configuration
-    pub fn configure_interrupts_pins(&mut self, config: PinConfig) -> Result<(), T::Error> {
+    pub fn configure_interrupts_pins(&mut self, config: PinConfig) -> Result<(), Error<T::Error>> {
         let ctrl_reg8 =
             self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG8.addr())?;
         self.modify_register(
@@ -310,9 +953,20 @@ where
 
     /// Get the current Accelerometer interrupt configuration
     pub fn get_accel_int_config(&mut self) -> Result<IntConfigAccel, T::Error> {
-        Ok(self
+        let mut config: IntConfigAccel = self
             .read_register(Sensor::Accelerometer, register::AG::INT_GEN_CFG_XL.addr())?
-            .into())
+            .into();
+        let ctrl_reg4 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG4.addr())?;
+        config.latch_interrupts = match ctrl_reg4 & accel_int::CfgBitmasks::LIR_XL1 {
+            x if x > 0 => IntLatch::Latched,
+            _ => IntLatch::NotLatched,
+        };
+        config.position_recognition = match ctrl_reg4 & accel_int::CfgBitmasks::_4D_XL1 {
+            x if x > 0 => PosRecog::_4D,
+            _ => PosRecog::_6D,
+        };
+        Ok(config)
     }
 
     /// Get the current Gyro interrupt configuration
@@ -329,18 +983,226 @@ where
             .into())
     }
 
+    /// Get the Accelerometer interrupt source (which axis and direction generated the interrupt).
+    ///
+    /// Reading INT_GEN_SRC_XL clears the interrupt request if it is latched (see `IntConfigAccel::latch_interrupts`).
+    pub fn get_accel_int_status(&mut self) -> Result<IntStatusAccel, T::Error> {
+        Ok(self
+            .read_register(Sensor::Accelerometer, register::AG::INT_GEN_SRC_XL.addr())?
+            .into())
+    }
+
+    /// Get the Gyro interrupt source (which axis and direction generated the interrupt).
+    ///
+    /// Reading INT_GEN_SRC_G clears the interrupt request if it is latched (see `IntConfigGyro::latch_interrupts`).
+    pub fn get_gyro_int_status(&mut self) -> Result<IntStatusGyro, T::Error> {
+        Ok(self
+            .read_register(Sensor::Gyro, register::AG::INT_GEN_SRC_G.addr())?
+            .into())
+    }
+
+    /// Get the Magnetometer interrupt source (which axis exceeded the threshold and in which direction).
+    ///
+    /// Reading INT_SRC_M clears the interrupt request if it is latched (see `IntConfigMag::interrupt_latching`).
+    pub fn get_mag_int_status(&mut self) -> Result<IntStatusMag, T::Error> {
+        Ok(self
+            .read_register(Sensor::Magnetometer, register::Mag::INT_SRC_M.addr())?
+            .into())
+    }
+
+    /// Acknowledge a latched Accelerometer interrupt by reading INT_GEN_SRC_XL
+    pub fn acknowledge_accel_int(&mut self) -> Result<(), T::Error> {
+        self.read_register(Sensor::Accelerometer, register::AG::INT_GEN_SRC_XL.addr())?;
+        Ok(())
+    }
+
+    /// Acknowledge a latched Gyro interrupt by reading INT_GEN_SRC_G
+    pub fn acknowledge_gyro_int(&mut self) -> Result<(), T::Error> {
+        self.read_register(Sensor::Gyro, register::AG::INT_GEN_SRC_G.addr())?;
+        Ok(())
+    }
+
+    /// Acknowledge a latched Magnetometer interrupt by reading INT_SRC_M
+    pub fn acknowledge_mag_int(&mut self) -> Result<(), T::Error> {
+        self.read_register(Sensor::Magnetometer, register::Mag::INT_SRC_M.addr())?;
+        Ok(())
+    }
+
+    /// Read every accelerometer/gyroscope interrupt and status source at once:
+    /// STATUS_REG, INT_GEN_SRC_XL, INT_GEN_SRC_G and FIFO_SRC.
+    ///
+    /// Reading the interrupt source registers clears latched interrupt requests.
+    pub fn poll_events(&mut self) -> Result<Events, T::Error> {
+        self.read_events(false)
+    }
+
+    /// Same as `poll_events()`, and also reads the Magnetometer interrupt source (INT_SRC_M).
+    pub fn poll_all_events(&mut self) -> Result<Events, T::Error> {
+        self.read_events(true)
+    }
+
+    fn read_events(&mut self, include_mag: bool) -> Result<Events, T::Error> {
+        let status_reg =
+            self.read_register(Sensor::Accelerometer, register::AG::STATUS_REG_1.addr())?;
+        let int_gen_src_xl =
+            self.read_register(Sensor::Accelerometer, register::AG::INT_GEN_SRC_XL.addr())?;
+        let int_gen_src_g = self.read_register(Sensor::Gyro, register::AG::INT_GEN_SRC_G.addr())?;
+        let fifo_src = self.read_register(Sensor::Accelerometer, register::AG::FIFO_SRC.addr())?;
+        let int_src_m = if include_mag {
+            Some(self.read_register(Sensor::Magnetometer, register::Mag::INT_SRC_M.addr())?)
+        } else {
+            None
+        };
+        Ok(Events::new(
+            status_reg,
+            int_gen_src_xl,
+            int_gen_src_g,
+            fifo_src,
+            int_src_m,
+        ))
+    }
+
     /// Configure Accelerometer interrupt
-    pub fn configure_interrupts_accel(&mut self, config: IntConfigAccel) -> Result<(), T::Error> {
+    pub fn configure_interrupts_accel(
+        &mut self,
+        config: IntConfigAccel,
+    ) -> Result<(), Error<T::Error>> {
+        let ctrl_reg4 =
+            self.read_register(Sensor::Accelerometer, register::AG::CTRL_REG4.addr())?;
+        self.modify_register(
+            config.ctrl_reg4_config(),
+            ctrl_reg4,
+            !accel_int::CfgBitmasks::CTRL_REG4_XL,
+        )?;
         self.write_register(config)
     }
 
+    /// Configure the Accelerometer interrupt generator for 4D/6D position recognition.
+    ///
+    /// All axes are compared with `threshold` (in g, converted with the current accelerometer scale).
+    /// In 4D mode, the Z-axis is not used. Use `get_orientation()` to read the recognized position.
+    pub fn configure_position_recognition(
+        &mut self,
+        mode: PosRecog,
+        threshold: f32,
+    ) -> Result<(), Error<T::Error>> {
+        self.configure_accel_int_threshold(IntThresholdAccel {
+            threshold_x: threshold,
+            threshold_y: threshold,
+            threshold_z: threshold,
+            ..Default::default()
+        })?;
+        self.configure_interrupts_accel(IntConfigAccel {
+            events_combination: Combination::And,
+            enable_6d: Flag::Enabled,
+            position_recognition: mode,
+            interrupt_zaxis_high: Flag::Enabled,
+            interrupt_zaxis_low: Flag::Enabled,
+            interrupt_yaxis_high: Flag::Enabled,
+            interrupt_yaxis_low: Flag::Enabled,
+            interrupt_xaxis_high: Flag::Enabled,
+            interrupt_xaxis_low: Flag::Enabled,
+            ..Default::default()
+        })
+    }
+
+    /// Get the device orientation recognized by the Accelerometer interrupt generator
+    /// (see `configure_position_recognition()`)
+    pub fn get_orientation(&mut self) -> Result<Orientation, T::Error> {
+        Ok(self.get_accel_int_status()?.orientation())
+    }
+
+    /// Set the Accelerometer interrupt thresholds (in g) and duration, converted with the current scale and data rate
+    pub fn configure_accel_int_threshold(
+        &mut self,
+        config: IntThresholdAccel,
+    ) -> Result<(), Error<T::Error>> {
+        self.write_converted(config.int_gen_ths_xl_config(self.accel.scale, self.accel_odr_hz()))?;
+        self.accel_int_threshold = Some(config);
+        Ok(())
+    }
+
+    /// Set the Gyro interrupt thresholds (in dps), counter mode and duration, converted with the current scale and data rate
+    pub fn configure_gyro_int_threshold(
+        &mut self,
+        config: IntThresholdGyro,
+    ) -> Result<(), Error<T::Error>> {
+        self.write_converted(config.int_gen_ths_g_config(self.gyro.scale, self.gyro.sample_rate))?;
+        self.gyro_int_threshold = Some(config);
+        Ok(())
+    }
+
+    /// Configure inactivity detection (threshold in g, duration, gyroscope mode), converted with the current scale and data rate
+    pub fn configure_inactivity(
+        &mut self,
+        config: InactivityConfig,
+    ) -> Result<(), Error<T::Error>> {
+        self.write_converted(config.act_ths_config(self.accel.scale, self.accel_odr_hz()))?;
+        self.inactivity = Some(config);
+        Ok(())
+    }
+
+    /// Write configurations converted from physical units, or return `Error::OutOfRange` if the conversion failed
+    fn write_converted<const N: usize>(
+        &mut self,
+        configs: Option<[Configuration; N]>,
+    ) -> Result<(), Error<T::Error>> {
+        for config in configs.ok_or(Error::OutOfRange)? {
+            self.write_register(config)?;
+        }
+        Ok(())
+    }
+
+    /// Write converted configurations, or the `clamped` ones if the conversion failed. Returns `false` if clamped.
+    fn write_clamped<const N: usize>(
+        &mut self,
+        configs: Option<[Configuration; N]>,
+        clamped: Option<[Configuration; N]>,
+    ) -> Result<bool, Error<T::Error>> {
+        let converted = configs.is_some();
+        self.write_converted(configs.or(clamped))?;
+        Ok(converted)
+    }
+
     /// Configure Gyro interrupt
-    pub fn configure_interrupts_gyro(&mut self, config: IntConfigGyro) -> Result<(), T::Error> {
+    pub fn configure_interrupts_gyro(
+        &mut self,
+        config: IntConfigGyro,
+    ) -> Result<(), Error<T::Error>> {
         self.write_register(config)
     }
 
+    /// Set the Magnetometer interrupt threshold (absolute value, in gauss).
+    ///
+    /// The value is converted with the current magnetometer scale and re-applied by `begin_mag()` and `set_mag_scale()`,
+    /// which clamp it and return `Error::OutOfRange` if the new scale cannot represent it.
+    /// Returns `Error::OutOfRange` if it cannot be represented with the current scale.
+    pub fn set_mag_int_threshold(&mut self, threshold: f32) -> Result<(), Error<T::Error>> {
+        let bytes = mag_int::int_ths_m(threshold, self.mag.scale).ok_or(Error::OutOfRange)?;
+        self.write_mag_int_threshold(bytes)?;
+        self.mag_int_threshold = Some(threshold);
+        Ok(())
+    }
+
+    /// Write INT_THS_L_M and INT_THS_H_M
+    fn write_mag_int_threshold(&mut self, bytes: [u8; 2]) -> Result<(), Error<T::Error>> {
+        self.write_register(Configuration {
+            sensor: Sensor::Magnetometer,
+            register: register::Mag::INT_THS_L_M.addr(),
+            value: bytes[0],
+        })?;
+        self.write_register(Configuration {
+            sensor: Sensor::Magnetometer,
+            register: register::Mag::INT_THS_H_M.addr(),
+            value: bytes[1],
+        })
+    }
+
     /// Configure Magnetometer interrupt
-    pub fn configure_interrupts_mag(&mut self, config: IntConfigMag) -> Result<(), T::Error> {
+    pub fn configure_interrupts_mag(
+        &mut self,
+        config: IntConfigMag,
+    ) -> Result<(), Error<T::Error>> {
         self.write_register(config)
     }
 
@@ -351,3 +1213,403 @@ where
         Ok(reg_data[0])
     }
 }
+
+/// Converts the high bytes of output registers (x, y, z) into raw readings
+fn to_xyz_high(bytes: &[u8]) -> (i16, i16, i16) {
+    (
+        (bytes[0] as i16) << 8,
+        (bytes[1] as i16) << 8,
+        (bytes[2] as i16) << 8,
+    )
+}
+
+/// Turns `Error::OutOfRange` into `Ok(false)`, so that the remaining settings can still be applied
+/// Converts 6 bytes of output registers (x, y, z; low byte first) into raw readings
+fn to_xyz(bytes: &[u8]) -> (i16, i16, i16) {
+    let x: i16 = (bytes[1] as i16) << 8 | bytes[0] as i16;
+    let y: i16 = (bytes[3] as i16) << 8 | bytes[2] as i16;
+    let z: i16 = (bytes[5] as i16) << 8 | bytes[4] as i16;
+    (x, y, z)
+}
+
+/// Converts a raw temperature reading into Celsius
+fn to_celsius(raw: i16) -> f32 {
+    (raw as f32) / TEMP_SCALE + TEMP_BIAS
+}
+
+#[test]
+fn convert_readings() {
+    assert_eq!(
+        to_xyz(&[0x01, 0x00, 0xFF, 0xFF, 0x00, 0x80]),
+        (1, -1, -32768)
+    );
+    assert_eq!(to_xyz_high(&[0x01, 0xFF, 0x80]), (256, -256, -32768));
+    assert_eq!(to_celsius(0), 25.0);
+    assert_eq!(to_celsius(-40), 22.5);
+}
+
+#[cfg(test)]
+fn mock_lsm9ds1() -> LSM9DS1<interface::mock::MockInterface> {
+    LSM9DS1Init::default().with_interface(interface::mock::MockInterface::new())
+}
+
+#[test]
+fn fifo_layout_from_ctrl_reg9() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    // FIFO set up before an MCU reset: the driver has no cached FIFO configuration
+    lsm9ds1.interface.ag[register::AG::CTRL_REG9.addr() as usize] = 0b0001_0010;
+    lsm9ds1.interface.ag[register::AG::FIFO_SRC.addr() as usize] = 1;
+    let temp = register::AG::OUT_TEMP_L.addr() as usize;
+    lsm9ds1.interface.ag[temp..temp + 2].copy_from_slice(&[0x10, 0x00]);
+    let gyro = register::AG::OUT_X_L_G.addr() as usize;
+    lsm9ds1.interface.ag[gyro..gyro + 6].copy_from_slice(&[1, 0, 2, 0, 3, 0]);
+
+    let mut frames = [ImuFrame::default(); 2];
+    assert_eq!(lsm9ds1.read_fifo(&mut frames), Ok(1));
+    assert_eq!(frames[0].temperature_c, Some(26.0));
+    assert_eq!(frames[0].gyro, (1, 2, 3));
+    assert!(lsm9ds1
+        .interface
+        .reads()
+        .contains(&(register::AG::CTRL_REG9.addr(), 1)));
+}
+
+#[test]
+fn fifo_overrun_accounting() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    // overrun, 32 samples stored; the gyroscope runs at 952 Hz
+    lsm9ds1.interface.ag[register::AG::FIFO_SRC.addr() as usize] = 0b0110_0000;
+
+    assert_eq!(
+        lsm9ds1.recover_fifo_overrun(OverrunPolicy::Count, 100_000),
+        Ok(63)
+    );
+    // not drained in between: only the samples lost since the previous call are added
+    assert_eq!(
+        lsm9ds1.recover_fifo_overrun(OverrunPolicy::Count, 110_000),
+        Ok(9)
+    );
+    assert_eq!(lsm9ds1.fifo_dropped_samples(), 72);
+
+    // the FIFO has been emptied: a new overrun is counted from scratch
+    lsm9ds1.fifo_emptied();
+    lsm9ds1.set_decimation(Decimate::_4samples).unwrap();
+    assert_eq!(
+        lsm9ds1.fifo_sample_clock().period_us(),
+        SampleClock::new(952.0, Decimate::_4samples).period_us()
+    );
+    assert_eq!(
+        lsm9ds1.recover_fifo_overrun(OverrunPolicy::Count, 400_000),
+        Ok(63)
+    );
+    assert_eq!(lsm9ds1.fifo_dropped_samples(), 135);
+}
+
+#[test]
+fn accel_only_fifo_frames() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    assert_eq!(lsm9ds1.accel_odr_hz(), 952.0);
+
+    lsm9ds1.interface.ag[register::AG::CTRL_REG1_G.addr() as usize] = 0xFF;
+    lsm9ds1.begin_accel_only().unwrap();
+    assert!(lsm9ds1.is_accel_only());
+    assert_eq!(
+        lsm9ds1.interface.ag[register::AG::CTRL_REG1_G.addr() as usize] & GyroBitmasks::ODR_G,
+        0
+    );
+    assert_eq!(lsm9ds1.accel_odr_hz(), 119.0);
+
+    lsm9ds1.interface.ag[register::AG::FIFO_SRC.addr() as usize] = 1;
+    let accel = register::AG::OUT_X_L_XL.addr() as usize;
+    lsm9ds1.interface.ag[accel..accel + 6].copy_from_slice(&[4, 0, 5, 0, 6, 0]);
+    let gyro = register::AG::OUT_X_L_G.addr() as usize;
+    lsm9ds1.interface.ag[gyro..gyro + 6].copy_from_slice(&[1, 0, 2, 0, 3, 0]);
+
+    let mut frames = [ImuFrame::default(); 1];
+    assert_eq!(lsm9ds1.read_fifo(&mut frames), Ok(1));
+    assert_eq!(frames[0].accel, (4, 5, 6));
+    assert_eq!(frames[0].gyro, (0, 0, 0));
+    let reads = lsm9ds1.interface.reads();
+    assert_eq!(reads.last(), Some(&(register::AG::OUT_X_L_XL.addr(), 6)));
+    assert!(!reads
+        .iter()
+        .any(|&(addr, _)| addr == register::AG::OUT_X_L_G.addr()));
+}
+
+#[test]
+fn accel_setters() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let ctrl_reg6 = register::AG::CTRL_REG6_XL.addr() as usize;
+    let ths_x = register::AG::INT_GEN_THS_X_XL.addr() as usize;
+    lsm9ds1.interface.ag[ctrl_reg6] = 0b1100_0111;
+
+    lsm9ds1.set_accel_scale(accel::Scale::_8G).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg6], 0b1101_1111);
+    assert!(matches!(lsm9ds1.accel_scale(), accel::Scale::_8G));
+
+    lsm9ds1.set_accel_odr(accel::ODR::_50Hz).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg6], 0b0101_1111);
+    assert!(matches!(lsm9ds1.accel.sample_rate, accel::ODR::_50Hz));
+
+    lsm9ds1
+        .set_accel_bandwidth(accel::BandwidthSelection::ByODR, accel::Bandwidth::_211Hz)
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg6], 0b0101_1001);
+
+    // a failed write leaves the driver's settings unchanged
+    lsm9ds1.set_verify_writes(true);
+    lsm9ds1.interface.stuck_ag = Some(ctrl_reg6 as u8);
+    assert!(lsm9ds1
+        .set_accel_bandwidth(accel::BandwidthSelection::ByBW, accel::Bandwidth::_50Hz)
+        .is_err());
+    assert!(matches!(lsm9ds1.accel.bandwidth, accel::Bandwidth::_211Hz));
+    lsm9ds1.interface.stuck_ag = None;
+
+    // thresholds in g are re-applied with the new scale
+    lsm9ds1
+        .configure_accel_int_threshold(IntThresholdAccel {
+            threshold_x: 3.0,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ths_x], 48);
+    lsm9ds1.set_accel_scale(accel::Scale::_16G).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ths_x], 16);
+    assert_eq!(
+        lsm9ds1.set_accel_scale(accel::Scale::_2G),
+        Err(Error::OutOfRange)
+    );
+    assert!(matches!(lsm9ds1.accel_scale(), accel::Scale::_2G));
+    assert_eq!(lsm9ds1.interface.ag[ths_x], 0x7F);
+    // the stored threshold is kept, so it is restored exactly with a larger scale
+    lsm9ds1.set_accel_scale(accel::Scale::_16G).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ths_x], 16);
+}
+
+#[test]
+fn gyro_setters() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let ctrl_reg1 = register::AG::CTRL_REG1_G.addr() as usize;
+    let ctrl_reg3 = register::AG::CTRL_REG3_G.addr() as usize;
+    let ths_xl = register::AG::INT_GEN_THS_XL_G.addr() as usize;
+    lsm9ds1.interface.ag[ctrl_reg1] = 0b0000_0011;
+    lsm9ds1.interface.ag[ctrl_reg3] = 0b1000_0000;
+
+    lsm9ds1.set_gyro_scale(gyro::Scale::_2000DPS).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg1], 0b0001_1011);
+    assert!(matches!(lsm9ds1.gyro_scale(), gyro::Scale::_2000DPS));
+    lsm9ds1.set_gyro_odr(gyro::ODR::_119Hz).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg1], 0b0111_1011);
+    lsm9ds1.set_gyro_bandwidth(gyro::Bandwidth::LPF_1).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg1], 0b0111_1001);
+
+    lsm9ds1
+        .set_gyro_hpf(gyro::HpFilter::Enabled, gyro::HpFilterCutoff::HPCF_4)
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg3], 0b1100_0011);
+    lsm9ds1
+        .set_gyro_low_power(gyro::LowPowerMode::Disabled)
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ctrl_reg3], 0b0100_0011);
+
+    // a failed write leaves the driver's settings unchanged
+    lsm9ds1.set_verify_writes(true);
+    lsm9ds1.interface.stuck_ag = Some(ctrl_reg3 as u8);
+    assert!(lsm9ds1
+        .set_gyro_hpf(gyro::HpFilter::Disabled, gyro::HpFilterCutoff::HPCF_1)
+        .is_err());
+    assert!(matches!(lsm9ds1.gyro.hpf_mode, gyro::HpFilter::Enabled));
+    assert!(matches!(
+        lsm9ds1.gyro.hpf_cutoff,
+        gyro::HpFilterCutoff::HPCF_4
+    ));
+    lsm9ds1.interface.stuck_ag = None;
+
+    // thresholds in dps are re-applied with the new scale
+    lsm9ds1
+        .configure_gyro_int_threshold(IntThresholdGyro {
+            threshold_x: 100.0,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ths_xl], 0x95);
+    lsm9ds1.set_gyro_scale(gyro::Scale::_245DPS).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[ths_xl], 0xA5);
+
+    lsm9ds1.set_gyro_scale(gyro::Scale::_2000DPS).unwrap();
+    lsm9ds1
+        .configure_gyro_int_threshold(IntThresholdGyro {
+            threshold_x: 500.0,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(
+        lsm9ds1.set_gyro_scale(gyro::Scale::_245DPS),
+        Err(Error::OutOfRange)
+    );
+    assert!(matches!(lsm9ds1.gyro_scale(), gyro::Scale::_245DPS));
+    assert_eq!(lsm9ds1.interface.ag[ths_xl - 1] & 0x7F, 0x3F);
+    assert_eq!(lsm9ds1.interface.ag[ths_xl], 0xFF);
+}
+
+#[test]
+fn mag_setters() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let ctrl_reg1 = register::Mag::CTRL_REG1_M.addr() as usize;
+    let ctrl_reg3 = register::Mag::CTRL_REG3_M.addr() as usize;
+    let ctrl_reg4 = register::Mag::CTRL_REG4_M.addr() as usize;
+    let ths = register::Mag::INT_THS_L_M.addr() as usize;
+    lsm9ds1.interface.mag[ctrl_reg1] = 0b0000_0001;
+    lsm9ds1.interface.mag[ctrl_reg3] = 0b1000_0100;
+    lsm9ds1.interface.mag[ctrl_reg4] = 0b0000_0010;
+
+    lsm9ds1.set_mag_odr(mag::ODR::_80Hz).unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg1], 0b0001_1101);
+    lsm9ds1.set_mag_xy_mode(mag::OpModeXY::UltraHigh).unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg1], 0b0111_1101);
+    lsm9ds1
+        .set_mag_temp_compensation(mag::TempComp::Enabled)
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg1], 0b1111_1101);
+    lsm9ds1
+        .set_mag_system_op(mag::SysOpMode::PowerDown)
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg3], 0b1000_0111);
+    lsm9ds1.set_mag_z_mode(mag::OpModeZ::High).unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg4], 0b0000_1010);
+
+    // the threshold in gauss is re-applied with the new scale
+    lsm9ds1.set_mag_int_threshold(2.0).unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ths..ths + 2], [0xCE, 0x37]);
+    lsm9ds1.set_mag_scale(mag::Scale::_16G).unwrap();
+    assert_eq!(
+        lsm9ds1.interface.mag[register::Mag::CTRL_REG2_M.addr() as usize],
+        0b0110_0000
+    );
+    assert!(matches!(lsm9ds1.mag.scale, mag::Scale::_16G));
+    assert_eq!(lsm9ds1.interface.mag[ths..ths + 2], [0x78, 0x0D]);
+
+    // a threshold beyond the new full scale is clamped
+    lsm9ds1.set_mag_int_threshold(6.0).unwrap();
+    assert_eq!(
+        lsm9ds1.set_mag_scale(mag::Scale::_4G),
+        Err(Error::OutOfRange)
+    );
+    assert!(matches!(lsm9ds1.mag.scale, mag::Scale::_4G));
+    assert_eq!(lsm9ds1.interface.mag[ths..ths + 2], [0xFF, 0x7F]);
+}
+
+#[test]
+fn verify_writes() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let ctrl_reg6 = register::AG::CTRL_REG6_XL.addr();
+    lsm9ds1.interface.ag[ctrl_reg6 as usize] = 0b1100_0111;
+    lsm9ds1.set_verify_writes(true);
+
+    // the written value is read back
+    lsm9ds1.set_accel_scale(accel::Scale::_8G).unwrap();
+    assert_eq!(lsm9ds1.interface.reads().last(), Some(&(ctrl_reg6, 1)));
+
+    lsm9ds1.interface.stuck_ag = Some(ctrl_reg6);
+    assert_eq!(
+        lsm9ds1.set_accel_scale(accel::Scale::_16G),
+        Err(Error::Verify {
+            sensor: Sensor::Accelerometer,
+            register: ctrl_reg6,
+            expected: 0b1100_1111,
+            found: 0b1101_1111,
+        })
+    );
+    assert!(matches!(lsm9ds1.accel_scale(), accel::Scale::_8G));
+}
+
+#[test]
+fn mag_fast_read() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let ctrl_reg5 = register::Mag::CTRL_REG5_M.addr() as usize;
+    let out_x_l = register::Mag::OUT_X_L_M.addr();
+    lsm9ds1.interface.mag[out_x_l as usize..out_x_l as usize + 6]
+        .copy_from_slice(&[0x34, 0x12, 0x78, 0x56, 0xBC, 0x9A]);
+
+    // without fast read, the full-resolution output is read
+    assert_eq!(
+        lsm9ds1.read_mag_fast_raw(),
+        Ok((0x1234, 0x5678, 0x9ABCu16 as i16))
+    );
+    assert_eq!(lsm9ds1.interface.reads().last(), Some(&(out_x_l, 6)));
+
+    lsm9ds1
+        .set_mag_block_data_update(mag::BlockDataUpdate::Blocked)
+        .unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg5], 0b0100_0000);
+    assert!(matches!(
+        lsm9ds1.mag.block_data_update,
+        mag::BlockDataUpdate::Blocked
+    ));
+    lsm9ds1.set_mag_fast_read(mag::FastRead::Enabled).unwrap();
+    assert_eq!(lsm9ds1.interface.mag[ctrl_reg5], 0b1100_0000);
+    assert!(matches!(lsm9ds1.mag.fast_read, mag::FastRead::Enabled));
+
+    // with fast read, only the high bytes are read
+    lsm9ds1.read_mag_fast_raw().unwrap();
+    assert_eq!(
+        lsm9ds1.interface.reads().last(),
+        Some(&(register::Mag::OUT_X_H_M.addr(), 3))
+    );
+}
+
+#[test]
+fn thresholds_while_powered_down() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let dur_g = register::AG::INT_GEN_DUR_G.addr() as usize;
+    let dur_xl = register::AG::INT_GEN_DUR_XL.addr() as usize;
+    lsm9ds1
+        .configure_gyro_int_threshold(IntThresholdGyro {
+            duration: interrupts::IntDuration::Milliseconds(50.0),
+            ..Default::default()
+        })
+        .unwrap();
+    lsm9ds1
+        .configure_accel_int_threshold(IntThresholdAccel {
+            duration: interrupts::IntDuration::Milliseconds(50.0),
+            ..Default::default()
+        })
+        .unwrap();
+
+    // durations cannot be converted at 0 Hz, the thresholds are left untouched
+    lsm9ds1.set_gyro_odr(gyro::ODR::PowerDown).unwrap();
+    lsm9ds1.set_accel_odr(accel::ODR::PowerDown).unwrap();
+    lsm9ds1.begin_accel_only().unwrap();
+    assert_eq!(lsm9ds1.interface.ag[dur_g], 48);
+
+    lsm9ds1.set_accel_odr(accel::ODR::_119Hz).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[dur_xl], 6);
+    lsm9ds1.set_gyro_odr(gyro::ODR::_238Hz).unwrap();
+    assert_eq!(lsm9ds1.interface.ag[dur_g], 12);
+    assert_eq!(lsm9ds1.interface.ag[dur_xl], 12);
+}
+
+#[test]
+fn reset_fifo_after_mcu_reset() {
+    let mut lsm9ds1 = mock_lsm9ds1();
+    let fifo_ctrl = register::AG::FIFO_CTRL.addr();
+    // FIFO set up in Continuous mode before an MCU reset, then overrun
+    lsm9ds1.interface.ag[fifo_ctrl as usize] = 0b1101_1111;
+    lsm9ds1.interface.ag[register::AG::CTRL_REG9.addr() as usize] = 0b0000_0010;
+    lsm9ds1.interface.ag[register::AG::FIFO_SRC.addr() as usize] = 0b0110_0000;
+
+    assert_eq!(
+        lsm9ds1.recover_fifo_overrun(OverrunPolicy::Reset, 100_000),
+        Ok(63)
+    );
+    assert!(lsm9ds1.interface.reads().contains(&(fifo_ctrl, 1)));
+    assert!(matches!(
+        lsm9ds1.fifo,
+        Some(FIFOConfig {
+            fifo_mode: FIFOMode::Continuous,
+            fifo_threshold: 32,
+            ..
+        })
+    ));
+    assert_eq!(lsm9ds1.interface.ag[fifo_ctrl as usize], 0b1101_1111);
+}
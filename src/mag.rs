@@ -2,7 +2,7 @@
 #![allow(dead_code, non_camel_case_types)]
 
 /// Magnetometer settings. Use this struct to configure the sensor.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct MagSettings {
     /// Output data rate selection
     pub sample_rate: ODR,
@@ -22,6 +22,10 @@ pub struct MagSettings {
     pub spi_mode: SpiMode,
     /// Z-axis operative mode selection
     pub z_performance: OpModeZ,
+    /// Fast read (high bytes of the output registers only)
+    pub fast_read: FastRead,
+    /// Block data update for magnetic data
+    pub block_data_update: BlockDataUpdate,
 }
 
 impl Default for MagSettings {
@@ -36,11 +40,83 @@ impl Default for MagSettings {
             low_power: LowPowerMode::Disabled,
             spi_mode: SpiMode::RW,
             z_performance: OpModeZ::Low,
+            fast_read: FastRead::Disabled,
+            block_data_update: BlockDataUpdate::Continuous,
         }
     }
 }
 
 impl MagSettings {
+    /// Decodes the settings from the values of CTRL_REG1_M ... CTRL_REG5_M
+    pub(crate) fn from_registers(
+        ctrl_reg1_m: u8,
+        ctrl_reg2_m: u8,
+        ctrl_reg3_m: u8,
+        ctrl_reg4_m: u8,
+        ctrl_reg5_m: u8,
+    ) -> Self {
+        MagSettings {
+            sample_rate: match (ctrl_reg1_m & MagBitmasks::DO) >> 2 {
+                0b000 => ODR::_0_625Hz,
+                0b001 => ODR::_1_25Hz,
+                0b010 => ODR::_2_5Hz,
+                0b011 => ODR::_5Hz,
+                0b100 => ODR::_10Hz,
+                0b101 => ODR::_20Hz,
+                0b110 => ODR::_40Hz,
+                _ => ODR::_80Hz,
+            },
+            temp_compensation: match ctrl_reg1_m & MagBitmasks::TEMP_COMP {
+                0 => TempComp::Disabled,
+                _ => TempComp::Enabled,
+            },
+            x_y_performance: match (ctrl_reg1_m & MagBitmasks::OM) >> 5 {
+                0b00 => OpModeXY::Low,
+                0b01 => OpModeXY::Medium,
+                0b10 => OpModeXY::High,
+                _ => OpModeXY::UltraHigh,
+            },
+            scale: match (ctrl_reg2_m & MagBitmasks::FS) >> 5 {
+                0b00 => Scale::_4G,
+                0b01 => Scale::_8G,
+                0b10 => Scale::_12G,
+                _ => Scale::_16G,
+            },
+            i2c_mode: match ctrl_reg3_m & (1 << 7) {
+                0 => I2cMode::Enabled,
+                _ => I2cMode::Disabled,
+            },
+            system_op: match ctrl_reg3_m & MagBitmasks::MD {
+                0b00 => SysOpMode::Continuous,
+                0b01 => SysOpMode::Single,
+                // 10 and 11 are both power-down
+                _ => SysOpMode::PowerDown,
+            },
+            low_power: match ctrl_reg3_m & (1 << 5) {
+                0 => LowPowerMode::Disabled,
+                _ => LowPowerMode::Enabled,
+            },
+            spi_mode: match ctrl_reg3_m & (1 << 2) {
+                0 => SpiMode::RW,
+                _ => SpiMode::W,
+            },
+            z_performance: match (ctrl_reg4_m & MagBitmasks::OMZ) >> 2 {
+                0b00 => OpModeZ::Low,
+                0b01 => OpModeZ::Medium,
+                0b10 => OpModeZ::High,
+                _ => OpModeZ::UltraHigh,
+            },
+            fast_read: match ctrl_reg5_m & MagBitmasks::FAST_READ {
+                0 => FastRead::Disabled,
+                _ => FastRead::Enabled,
+            },
+            block_data_update: match ctrl_reg5_m & MagBitmasks::BDU {
+                0 => BlockDataUpdate::Continuous,
+                _ => BlockDataUpdate::Blocked,
+            },
+        }
+    }
+
     /// Returns `u8` to write to CTRL_REG1_M. See page 63.
     /// # CTRL_REG1_M: [TEMP_COMP][OM1][OM0][DO2][DO1][DO0][0][ST]
     /// - TEMP_COMP - Temperature compensation
@@ -93,12 +169,63 @@ impl MagSettings {
     }
 
     /// Returns `u8` to write to CTRL_REG5_M. See page 65.
-    /// # CTRL_REG5_M: [0][BDU][0][0][0][0][0][0]
-    /// - BDU - Block data update for magnetic data // TODO
+    /// # CTRL_REG5_M: [FAST_READ][BDU][0][0][0][0][0][0]
+    /// - FAST_READ - Fast read (0:disabled, 1:enabled)
+    /// - BDU - Block data update for magnetic data
     ///     - 0:continuous
     ///     - 1:not updated until MSB/LSB are read
     pub fn ctrl_reg5_m(&self) -> u8 {
-        0x00 // TODO
+        self.fast_read.value() | self.block_data_update.value()
+    }
+}
+
+/// Bitmasks for the fields of CTRL_REG1_M ... CTRL_REG5_M
+pub(crate) struct MagBitmasks;
+
+impl MagBitmasks {
+    /// CTRL_REG1_M TEMP_COMP
+    pub(crate) const TEMP_COMP: u8 = 0b1000_0000;
+    /// CTRL_REG1_M OM[1:0]
+    pub(crate) const OM: u8 = 0b0110_0000;
+    /// CTRL_REG1_M DO[2:0]
+    pub(crate) const DO: u8 = 0b0001_1100;
+    /// CTRL_REG2_M FS[1:0]
+    pub(crate) const FS: u8 = 0b0110_0000;
+    /// CTRL_REG3_M MD[1:0]
+    pub(crate) const MD: u8 = 0b0000_0011;
+    /// CTRL_REG4_M OMZ[1:0]
+    pub(crate) const OMZ: u8 = 0b0000_1100;
+    /// CTRL_REG5_M FAST_READ
+    pub(crate) const FAST_READ: u8 = 0b1000_0000;
+    /// CTRL_REG5_M BDU
+    pub(crate) const BDU: u8 = 0b0100_0000;
+}
+
+/// Fast read enable: allows reading the high bytes of the output registers only. (Refer to Table 121)
+#[derive(Debug, Clone, Copy)]
+pub enum FastRead {
+    Disabled = 0,
+    Enabled = 1,
+}
+
+impl FastRead {
+    pub fn value(self) -> u8 {
+        (self as u8) << 7
+    }
+}
+
+/// Block data update for magnetic data. (Refer to Table 121)
+#[derive(Debug, Clone, Copy)]
+pub enum BlockDataUpdate {
+    /// Continuous update
+    Continuous = 0,
+    /// Output registers not updated until MSB and LSB have been read
+    Blocked = 1,
+}
+
+impl BlockDataUpdate {
+    pub fn value(self) -> u8 {
+        (self as u8) << 6
     }
 }
 
@@ -340,3 +467,47 @@ fn mag_set_odr() {
     };
     assert_eq!(mag.ctrl_reg1_m() & mask, 0b0001_1100);
 }
+
+#[test]
+fn mag_settings_from_registers() {
+    let settings = MagSettings {
+        temp_compensation: TempComp::Enabled,
+        x_y_performance: OpModeXY::High,
+        sample_rate: ODR::_2_5Hz,
+        scale: Scale::_12G,
+        i2c_mode: I2cMode::Disabled,
+        system_op: SysOpMode::Single,
+        low_power: LowPowerMode::Enabled,
+        spi_mode: SpiMode::W,
+        z_performance: OpModeZ::Medium,
+        fast_read: FastRead::Enabled,
+        block_data_update: BlockDataUpdate::Blocked,
+    };
+    let decoded = MagSettings::from_registers(
+        settings.ctrl_reg1_m(),
+        settings.ctrl_reg2_m(),
+        settings.ctrl_reg3_m(),
+        settings.ctrl_reg4_m(),
+        settings.ctrl_reg5_m(),
+    );
+    assert_eq!(decoded.ctrl_reg1_m(), settings.ctrl_reg1_m());
+    assert_eq!(decoded.ctrl_reg2_m(), settings.ctrl_reg2_m());
+    assert_eq!(decoded.ctrl_reg3_m(), settings.ctrl_reg3_m());
+    assert_eq!(decoded.ctrl_reg4_m(), settings.ctrl_reg4_m());
+    assert_eq!(decoded.ctrl_reg5_m(), settings.ctrl_reg5_m());
+}
+
+#[test]
+fn mag_set_ctrl_reg5() {
+    let mag = MagSettings {
+        block_data_update: BlockDataUpdate::Blocked,
+        ..Default::default()
+    };
+    assert_eq!(mag.ctrl_reg5_m(), 0b0100_0000);
+
+    let mag = MagSettings {
+        fast_read: FastRead::Enabled,
+        ..Default::default()
+    };
+    assert_eq!(mag.ctrl_reg5_m(), 0b1000_0000);
+}